@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+/* Every Unlockable Achievement */
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum Achievement {
+    FirstWin,
+    NoTies,
+    Flawless,
+    HardAiBestOf7,
+    EveryGesture,
+    Comeback,
+    HatTrick,
+    SpeedDemon,
+    Marathon,
+}
+
+impl Achievement {
+    pub const ALL: [Achievement; 9] = [
+        Achievement::FirstWin,
+        Achievement::NoTies,
+        Achievement::Flawless,
+        Achievement::HardAiBestOf7,
+        Achievement::EveryGesture,
+        Achievement::Comeback,
+        Achievement::HatTrick,
+        Achievement::SpeedDemon,
+        Achievement::Marathon,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Achievement::FirstWin => "First Blood",
+            Achievement::NoTies => "No Stalemates",
+            Achievement::Flawless => "Flawless",
+            Achievement::HardAiBestOf7 => "Machine Breaker",
+            Achievement::EveryGesture => "Full Arsenal",
+            Achievement::Comeback => "Never Give Up",
+            Achievement::HatTrick => "Hat Trick",
+            Achievement::SpeedDemon => "Speed Demon",
+            Achievement::Marathon => "Marathon",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Achievement::FirstWin => "Win your first match",
+            Achievement::NoTies => "Win a match without a single tie",
+            Achievement::Flawless => "Win a match without losing a round",
+            Achievement::HardAiBestOf7 => "Beat Hard AI in a best-of-7 (or longer)",
+            Achievement::EveryGesture => "Win a round with every gesture in one match",
+            Achievement::Comeback => "Win a match after trailing 0-3",
+            Achievement::HatTrick => "Win 3 matches in a row",
+            Achievement::SpeedDemon => "Win a blitz round with a sub-second throw",
+            Achievement::Marathon => "Play a match of 15 rounds or more",
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod achievement;
+pub mod tracker;
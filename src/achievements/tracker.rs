@@ -0,0 +1,359 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::achievement::Achievement;
+use crate::game::match_settings::MatchFormat;
+use crate::game::round::{RoundRecord, RoundResult, SUB_SECOND_MS};
+use crate::game::rules::Gesture;
+use crate::history::match_record::MatchRecord;
+use crate::io::save_load::load_achievements_only;
+use crate::io::storage::Storage;
+use crate::players::ai_difficulty::AiDifficulty;
+use crate::players::player::PlayerType;
+use crate::scoreboard::scoreboard::Scoreboard;
+use crate::scoreboard::stats::MatchOutcome;
+
+/* One Unlock - when and in which match */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Unlock {
+    pub achievement: Achievement,
+    pub unlocked_at: DateTime<Utc>,
+    pub match_id: String,
+}
+
+/* Everyone's Unlocks (saved next to the scoreboard, keyed by profile ID) */
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AchievementBook {
+    pub players: HashMap<String, Vec<Unlock>>,
+
+    /* Set on load when the file was changed outside the game: the players whose unlocks were edited.
+     * Never saved - see Scoreboard::tampering.
+    */
+    #[serde(skip)]
+    pub tampering: Option<Vec<String>>,
+}
+
+impl AchievementBook {
+    pub fn load(storage: &dyn Storage, scoreboard: &Scoreboard) -> Self {
+        load_achievements_only(storage, scoreboard)
+    }
+
+    pub fn save(&self, storage: &dyn Storage) -> Result<(), String> {
+        storage.save_achievements(self)
+    }
+
+    pub fn unlocks_for(&self, player_id: &str) -> &[Unlock] {
+        self.players.get(player_id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /* Moves `from`'s unlocks to `into` (profile merge) - the earlier unlock of a duplicate wins */
+    pub fn merge_players(&mut self, from_id: &str, into_id: &str) {
+        let Some(moved) = self.players.remove(from_id) else {
+            return;
+        };
+
+        let unlocks = self.players.entry(into_id.to_string()).or_default();
+        for unlock in moved {
+            match unlocks.iter_mut().find(|u| u.achievement == unlock.achievement) {
+                Some(existing) if existing.unlocked_at <= unlock.unlocked_at => {}
+                Some(existing) => *existing = unlock,
+                None => unlocks.push(unlock),
+            }
+        }
+        unlocks.sort_by_key(|u| u.unlocked_at);
+    }
+
+    // Older books were keyed by player name - move those under the matching profile
+    pub fn migrate_legacy_names(&mut self, scoreboard: &Scoreboard) {
+        let legacy: Vec<String> = self
+            .players
+            .keys()
+            .filter(|key| !scoreboard.profiles.contains_key(*key))
+            .cloned()
+            .collect();
+
+        for name in legacy {
+            if let Some(profile) = scoreboard.find_profile(&name) {
+                self.merge_players(&name, &profile.id);
+            }
+        }
+    }
+
+    /* Runs a finished match past every achievement >> (player name, achievement) newly unlocked
+     * `scoreboard` must already include this match (profiles and streaks are read from it).
+    */
+    pub fn unlock_from_match(
+        &mut self,
+        record: &MatchRecord,
+        scoreboard: &Scoreboard,
+    ) -> Vec<(String, Achievement)> {
+        let mut unlocked = Vec::new();
+
+        for is_p1 in [true, false] {
+            let (player, opponent) = if is_p1 {
+                (&record.player1, &record.player2)
+            } else {
+                (&record.player2, &record.player1)
+            };
+
+            /* Only people collect achievements */
+            if player.player_type != PlayerType::Human {
+                continue;
+            }
+            let Some(player_id) = scoreboard.profile_id(player) else {
+                continue;
+            };
+
+            let view = PlayerView { record, is_p1 };
+            let earned: Vec<Achievement> = Achievement::ALL
+                .into_iter()
+                .filter(|a| match a {
+                    Achievement::HatTrick => scoreboard
+                        .players
+                        .get(player_id)
+                        .is_some_and(|s| s.streaks.current_wins >= 3),
+                    Achievement::HardAiBestOf7 => {
+                        view.won()
+                            && opponent.player_type == PlayerType::Computer(AiDifficulty::Hard)
+                            && matches!(record.match_format, MatchFormat::BestOf(n) if n >= 7)
+                    }
+                    other => view.earned(*other),
+                })
+                .collect();
+
+            let unlocks = self.players.entry(player_id.to_string()).or_default();
+            for achievement in earned {
+                if unlocks.iter().any(|u| u.achievement == achievement) {
+                    continue;
+                }
+                unlocks.push(Unlock {
+                    achievement,
+                    unlocked_at: record.played_at,
+                    match_id: record.id.clone(),
+                });
+                unlocked.push((player.name.clone(), achievement));
+            }
+        }
+
+        unlocked
+    }
+}
+
+/* A Finished Match Seen From One Player's Side */
+struct PlayerView<'a> {
+    record: &'a MatchRecord,
+    is_p1: bool,
+}
+
+impl PlayerView<'_> {
+    fn won(&self) -> bool {
+        self.record.outcome
+            == if self.is_p1 {
+                MatchOutcome::Player1Win
+            } else {
+                MatchOutcome::Player2Win
+            }
+    }
+
+    fn won_round(&self, round: &RoundRecord) -> bool {
+        round.result
+            == if self.is_p1 {
+                RoundResult::Player1Win
+            } else {
+                RoundResult::Player2Win
+            }
+    }
+
+    fn lost_round(&self, round: &RoundRecord) -> bool {
+        round.result != RoundResult::Tie && !self.won_round(round)
+    }
+
+    fn gesture(&self, round: &RoundRecord) -> Option<Gesture> {
+        if self.is_p1 {
+            round.gesture_p1.clone()
+        } else {
+            round.gesture_p2.clone()
+        }
+    }
+
+    fn reaction_ms(&self, round: &RoundRecord) -> Option<u64> {
+        if self.is_p1 {
+            round.reaction_ms_p1
+        } else {
+            round.reaction_ms_p2
+        }
+    }
+
+    fn earned(&self, achievement: Achievement) -> bool {
+        let rounds = &self.record.rounds;
+
+        match achievement {
+            Achievement::FirstWin => self.won(),
+            Achievement::NoTies => self.won() && rounds.iter().all(|r| r.result != RoundResult::Tie),
+            Achievement::Flawless => self.won() && !rounds.iter().any(|r| self.lost_round(r)),
+            Achievement::EveryGesture => {
+                let winning: HashSet<Gesture> = rounds
+                    .iter()
+                    .filter(|r| self.won_round(r))
+                    .filter_map(|r| self.gesture(r))
+                    .collect();
+                Gesture::gestures_for_ruleset(&self.record.ruleset)
+                    .iter()
+                    .all(|g| winning.contains(g))
+            }
+            Achievement::Comeback => self.won() && self.trailed_zero_three(),
+            Achievement::SpeedDemon => {
+                self.record.blitz.is_some()
+                    && rounds.iter().any(|r| {
+                        self.won_round(r)
+                            && self.reaction_ms(r).is_some_and(|ms| ms < SUB_SECOND_MS)
+                    })
+            }
+            Achievement::Marathon => rounds.len() >= 15,
+            // Need more than the match itself - decided in unlock_from_match
+            Achievement::HatTrick | Achievement::HardAiBestOf7 => false,
+        }
+    }
+
+    /* Was the player ever 0 rounds to 3 down? */
+    fn trailed_zero_three(&self) -> bool {
+        let mut mine = 0;
+        let mut theirs = 0;
+
+        for round in &self.record.rounds {
+            if self.won_round(round) {
+                mine += 1;
+            } else if self.lost_round(round) {
+                theirs += 1;
+            }
+            if mine == 0 && theirs >= 3 {
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::game_state::GameState;
+    use crate::game::match_settings::BlitzSettings;
+    use crate::game::round::{resolve_round, Throw};
+    use crate::game::rules::Ruleset;
+    use crate::players::player::Player;
+
+    use Gesture::{Paper, Rock, Scissors};
+
+    // Plays `rounds` (gesture pairs + Player 1's reaction time) and folds the match into `scoreboard`
+    fn play(
+        scoreboard: &mut Scoreboard,
+        players: (Player, Player),
+        match_format: MatchFormat,
+        blitz: Option<BlitzSettings>,
+        rounds: &[(Gesture, Gesture, u64)],
+    ) -> MatchRecord {
+        let mut state = GameState::new(players.0, players.1, Ruleset::Classic, match_format, None, blitz);
+        for (g1, g2, ms) in rounds {
+            let p1 = Throw { gesture: g1.clone(), reaction_ms: Some(*ms) };
+            let p2 = Throw { gesture: g2.clone(), reaction_ms: Some(5000) };
+            resolve_round(&mut state, p1, p2);
+        }
+        let outcome = match state.score_player1.cmp(&state.score_player2) {
+            std::cmp::Ordering::Greater => MatchOutcome::Player1Win,
+            std::cmp::Ordering::Less => MatchOutcome::Player2Win,
+            std::cmp::Ordering::Equal => MatchOutcome::Tie,
+        };
+        let record = MatchRecord::from_state(&state, outcome);
+        scoreboard.update_after_match(&record);
+        record
+    }
+
+    fn achievements(unlocked: &[(String, Achievement)]) -> Vec<Achievement> {
+        unlocked.iter().map(|(_, a)| *a).collect()
+    }
+
+    #[test]
+    fn flawless_best_of_7_against_hard_ai() {
+        let mut scoreboard = Scoreboard::new();
+        let mut book = AchievementBook::default();
+        let players = (Player::new_human("Ana"), Player::new_ai("Computer", AiDifficulty::Hard));
+        let rounds = [(Rock, Scissors, 2000), (Paper, Rock, 2000), (Scissors, Paper, 2000), (Rock, Scissors, 2000)];
+
+        let record = play(&mut scoreboard, players, MatchFormat::BestOf(7), None, &rounds);
+        let unlocked = book.unlock_from_match(&record, &scoreboard);
+        assert!(unlocked.iter().all(|(name, _)| name == "Ana"));
+        assert_eq!(
+            achievements(&unlocked),
+            [
+                Achievement::FirstWin,
+                Achievement::NoTies,
+                Achievement::Flawless,
+                Achievement::HardAiBestOf7,
+                Achievement::EveryGesture,
+            ]
+        );
+
+        // Each one unlocks once
+        assert!(book.unlock_from_match(&record, &scoreboard).is_empty());
+        let ana = scoreboard.find_profile("Ana").unwrap();
+        assert_eq!(book.unlocks_for(&ana.id).len(), 5);
+    }
+
+    #[test]
+    fn comeback_speed_and_hat_trick() {
+        let mut scoreboard = Scoreboard::new();
+        let mut book = AchievementBook::default();
+        let humans = || (Player::new_human("Ana"), Player::new_human("Ben"));
+        let blitz = Some(BlitzSettings { sub_second_bonus: false });
+
+        // 0-3 down, then a tie and four wins - the first of them in under a second
+        let comeback = [
+            (Rock, Paper, 2000),
+            (Rock, Paper, 2000),
+            (Rock, Paper, 2000),
+            (Rock, Rock, 5000),
+            (Paper, Rock, 800),
+            (Paper, Rock, 2000),
+            (Paper, Rock, 2000),
+            (Paper, Rock, 2000),
+        ];
+        let record = play(&mut scoreboard, humans(), MatchFormat::FirstTo(4), blitz, &comeback);
+        let unlocked = achievements(&book.unlock_from_match(&record, &scoreboard));
+        assert_eq!(unlocked, [Achievement::FirstWin, Achievement::Comeback, Achievement::SpeedDemon]);
+
+        // Two more wins make three in a row (sub-second, but not in blitz)
+        let win = [(Rock, Scissors, 500)];
+        let record = play(&mut scoreboard, humans(), MatchFormat::SingleRound, None, &win);
+        assert_eq!(
+            achievements(&book.unlock_from_match(&record, &scoreboard)),
+            [Achievement::NoTies, Achievement::Flawless]
+        );
+        let record = play(&mut scoreboard, humans(), MatchFormat::SingleRound, None, &win);
+        assert_eq!(achievements(&book.unlock_from_match(&record, &scoreboard)), [Achievement::HatTrick]);
+    }
+
+    #[test]
+    fn merging_players_keeps_the_earlier_unlock() {
+        let unlock = |achievement, days_ago| Unlock {
+            achievement,
+            unlocked_at: Utc::now() - chrono::Duration::days(days_ago),
+            match_id: format!("m{days_ago}"),
+        };
+        let mut book = AchievementBook::default();
+        book.players.insert("a".into(), vec![unlock(Achievement::FirstWin, 1)]);
+        book.players.insert("b".into(), vec![unlock(Achievement::FirstWin, 5), unlock(Achievement::Marathon, 3)]);
+
+        book.merge_players("b", "a");
+        assert!(book.unlocks_for("b").is_empty());
+        let merged: Vec<(Achievement, &str)> = book
+            .unlocks_for("a")
+            .iter()
+            .map(|u| (u.achievement, u.match_id.as_str()))
+            .collect();
+        assert_eq!(merged, [(Achievement::FirstWin, "m5"), (Achievement::Marathon, "m3")]);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::achievements::tracker::AchievementBook;
+use crate::display::colors::{accent, subtle, success, ColorSupport};
+use crate::io::storage::Storage;
+use crate::scoreboard::scoreboard::Scoreboard;
+
+/* `admin ...` subcommands >> None when the arguments don't match any of them */
+pub fn run_admin(
+    args: &[&str],
+    storage: &dyn Storage,
+    color_support: ColorSupport,
+) -> Option<Result<(), String>> {
+    let result = match args {
+        ["players"] => {
+            list_players(storage, color_support);
+            Ok(())
+        }
+        ["rename", name, new_name] => rename(storage, name, new_name, color_support),
+        ["merge", from, into] => merge(storage, from, into, color_support),
+        _ => return None,
+    };
+    Some(result)
+}
+
+fn list_players(storage: &dyn Storage, color_support: ColorSupport) {
+    let scoreboard = Scoreboard::load(storage);
+
+    let mut profiles: Vec<_> = scoreboard.profiles.values().collect();
+    profiles.sort_by_key(|p| p.display_name.to_lowercase());
+
+    if profiles.is_empty() {
+        println!("{}", subtle("No players yet.", color_support));
+        return;
+    }
+
+    for profile in profiles {
+        let matches = scoreboard
+            .players
+            .get(&profile.id)
+            .map_or(0, |s| s.matches_played);
+        let aliases = if profile.aliases.is_empty() {
+            String::new()
+        } else {
+            format!("  aka {}", profile.aliases.join(", "))
+        };
+        println!(
+            "{} {:<20} {:>4} matches{}",
+            accent(&profile.id, color_support),
+            profile.display_name,
+            matches,
+            subtle(&aliases, color_support)
+        );
+    }
+}
+
+fn rename(storage: &dyn Storage, name: &str, new_name: &str, color_support: ColorSupport) -> Result<(), String> {
+    let mut scoreboard = Scoreboard::load(storage);
+    let id = scoreboard.rename_player(name, new_name)?;
+    scoreboard.save(storage)?;
+
+    println!(
+        "{}",
+        success(
+            &format!("Renamed '{name}' to '{}'.", scoreboard.display_name(&id)),
+            color_support
+        )
+    );
+    Ok(())
+}
+
+fn merge(storage: &dyn Storage, from: &str, into: &str, color_support: ColorSupport) -> Result<(), String> {
+    let mut scoreboard = Scoreboard::load(storage);
+    let mut achievements = AchievementBook::load(storage, &scoreboard);
+
+    let (from_id, into_id) = scoreboard.merge_players(from, into)?;
+    achievements.merge_players(&from_id, &into_id);
+
+    scoreboard.save(storage)?;
+    achievements.save(storage)?;
+
+    println!(
+        "{}",
+        success(
+            &format!("Merged '{from}' into '{}'.", scoreboard.display_name(&into_id)),
+            color_support
+        )
+    );
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use crate::display::colors::{accent, header, subtle, ColorSupport};
+use crate::game::match_settings::{MatchFormat, TimeoutPenalty};
+use crate::io::config::{config_path, ColorMode, Config};
+use crate::io::storage::data_dir;
+use crate::players::profile::ColorTheme;
+
+/* `config` >> None when that doesn't parse */
+pub fn run_config(
+    args: &[&str],
+    config: &Config,
+    color_support: ColorSupport,
+) -> Option<Result<(), String>> {
+    if !args.is_empty() {
+        return None;
+    }
+    show_config(config, color_support);
+    Some(Ok(()))
+}
+
+/* Where the config file is looked for, and the settings in use (file + PL_* overrides) */
+fn show_config(config: &Config, color_support: ColorSupport) {
+    let path = config_path();
+    let found = if path.exists() { "" } else { " (not found - defaults in use)" };
+    println!("{}", header("Configuration", color_support));
+    println!("  {}{}", path.display(), subtle(found, color_support));
+
+    let not_set = || "not set".to_string();
+    let defaults = &config.defaults;
+    let rows = [
+        ("data dir", data_dir(config.data_dir.as_deref()).display().to_string()),
+        (
+            "color",
+            match config.color {
+                ColorMode::Auto => "auto",
+                ColorMode::Always => "always",
+                ColorMode::Never => "never",
+            }
+            .to_string(),
+        ),
+        (
+            "theme",
+            match config.theme {
+                ColorTheme::Neon => "neon",
+                ColorTheme::Plain => "plain",
+            }
+            .to_string(),
+        ),
+        ("ai name", config.ai_name.clone()),
+        (
+            "ruleset",
+            defaults.ruleset.as_ref().map_or_else(not_set, |r| format!("{r:?}").to_lowercase()),
+        ),
+        (
+            "format",
+            defaults.format.as_ref().map_or_else(not_set, |f| match f {
+                MatchFormat::SingleRound => "single".to_string(),
+                MatchFormat::BestOf(n) => format!("best-of-{n}"),
+                MatchFormat::FirstTo(k) => format!("first-to-{k}"),
+            }),
+        ),
+        (
+            "difficulty",
+            defaults.difficulty.as_ref().map_or_else(not_set, |d| format!("{d:?}").to_lowercase()),
+        ),
+        (
+            "timer",
+            defaults.turn_timer.map_or_else(
+                || "none".to_string(),
+                |timer| {
+                    let penalty = match timer.penalty {
+                        TimeoutPenalty::RandomGesture => "random",
+                        TimeoutPenalty::ForfeitRound => "forfeit-round",
+                        TimeoutPenalty::ForfeitMatch => "forfeit-match",
+                    };
+                    format!("{}s, {penalty} on timeout", timer.seconds)
+                },
+            ),
+        ),
+        (
+            "k-factor",
+            config.ratings.elo_k_factor.map_or_else(not_set, |k| k.to_string()),
+        ),
+        (
+            "rating period",
+            config.ratings.period_days.map_or_else(not_set, |d| format!("{d} days")),
+        ),
+    ];
+
+    println!();
+    for (label, value) in rows {
+        println!("  {} {value}", accent(&format!("{label:<14}"), color_support));
+    }
+}
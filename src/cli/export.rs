@@ -0,0 +1,78 @@
+use std::path::Path;
+
+use crate::history::query::MatchQuery;
+use crate::io::export::{ExportFormat, StandingRow, match_rows, render, standings};
+use crate::io::file_manager::write_atomic;
+use crate::io::storage::Storage;
+use crate::scoreboard::scoreboard::Scoreboard;
+
+/* `export scoreboard|matches [--format F] [--output FILE] [FILTERS]` >> None when that doesn't parse */
+pub fn run_export(args: &[&str], storage: &dyn Storage) -> Option<Result<(), String>> {
+    let (what, rest) = args.split_first()?;
+    if !matches!(*what, "scoreboard" | "matches") {
+        return None;
+    }
+    Some(export(what, rest, storage))
+}
+
+fn export(what: &str, args: &[&str], storage: &dyn Storage) -> Result<(), String> {
+    let mut format = None;
+    let mut output = None;
+    let mut filters = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match *arg {
+            "--format" => {
+                let value = args.next().ok_or("--format needs a value")?;
+                format = Some(ExportFormat::parse(value)?);
+            }
+            "--output" => output = Some(*args.next().ok_or("--output needs a value")?),
+            other => filters.push(other),
+        }
+    }
+
+    // No --format: go by the output file's extension, else Markdown
+    let format = format
+        .or_else(|| output.and_then(|file| ExportFormat::from_extension(Path::new(file))))
+        .unwrap_or(ExportFormat::Markdown);
+    let query = super::parse_match_filters(storage, &filters)?;
+    let scoreboard = Scoreboard::load(storage);
+
+    let text = if what == "scoreboard" {
+        render(&standings_for(&scoreboard, &query, storage)?, format)?
+    } else {
+        render(&match_rows(&scoreboard, &storage.find_matches(&query)?), format)?
+    };
+
+    match output {
+        Some(file) => {
+            write_atomic(file, &text).map_err(|e| format!("Write export failed: {e}"))?;
+            println!("Exported {what} to {file}");
+        }
+        None => print!("{text}"),
+    }
+    Ok(())
+}
+
+/* Ruleset or date filters: stats as if only those matches had been played
+ * (the player filter just picks that player's row - their opponents still count).
+*/
+fn standings_for(
+    scoreboard: &Scoreboard,
+    query: &MatchQuery,
+    storage: &dyn Storage,
+) -> Result<Vec<StandingRow>, String> {
+    let player_id = query.player_id.as_deref();
+    if query.ruleset.is_none() && query.from.is_none() && query.until.is_none() {
+        return Ok(standings(scoreboard, player_id));
+    }
+
+    let everyone = MatchQuery {
+        player_id: None,
+        player_names: Vec::new(),
+        ..query.clone()
+    };
+    let rebuilt = scoreboard.replayed(&storage.find_matches(&everyone)?);
+    Ok(standings(&rebuilt, player_id))
+}
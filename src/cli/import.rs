@@ -0,0 +1,171 @@
+use std::path::Path;
+
+use crate::display::colors::{accent, header, subtle, success, ColorSupport};
+use crate::game::rules::Ruleset;
+use crate::io::import::{plan_import, read_source, ImportPlan, MergeMode};
+use crate::io::storage::Storage;
+use crate::scoreboard::scoreboard::Scoreboard;
+use crate::scoreboard::stats::PlayerStats;
+
+/* `import PATH [--dry-run]` >> None when that doesn't parse */
+pub fn run_import(
+    args: &[&str],
+    storage: &dyn Storage,
+    color_support: ColorSupport,
+) -> Option<Result<(), String>> {
+    let (path, dry_run) = match args {
+        [path] | [path, "--dry-run"] | ["--dry-run", path] if !path.starts_with("--") => {
+            (*path, args.contains(&"--dry-run"))
+        }
+        _ => return None,
+    };
+    Some(import(Path::new(path), dry_run, storage, color_support))
+}
+
+fn import(
+    path: &Path,
+    dry_run: bool,
+    storage: &dyn Storage,
+    color_support: ColorSupport,
+) -> Result<(), String> {
+    let source = read_source(path)?;
+    if source.skipped_lines > 0 {
+        println!(
+            "{}",
+            subtle(
+                &format!("Skipped {} unreadable match log lines.", source.skipped_lines),
+                color_support
+            )
+        );
+    }
+
+    let local = Scoreboard::load(storage);
+    let local_matches = storage.load_matches()?;
+    let plan = plan_import(&local, &local_matches, source);
+    show_plan(&local, &plan, color_support);
+
+    if dry_run {
+        println!("{}", subtle("Dry run - nothing was saved.", color_support));
+        return Ok(());
+    }
+
+    for record in &plan.new_matches {
+        storage.append_match(record)?;
+    }
+    plan.scoreboard.save(storage)?;
+
+    println!(
+        "{}",
+        success(
+            &format!("Imported {} from {}.", matches(plan.new_matches.len()), path.display()),
+            color_support
+        )
+    );
+    Ok(())
+}
+
+fn show_plan(local: &Scoreboard, plan: &ImportPlan, color_support: ColorSupport) {
+    println!("{}", header("Players", color_support));
+    for (theirs, ours) in &plan.matched_players {
+        if theirs == ours {
+            println!("  {theirs}");
+        } else {
+            println!("  {theirs} {}", subtle(&format!("= {ours}"), color_support));
+        }
+    }
+    for name in &plan.new_players {
+        println!("  {name} {}", accent("(new)", color_support));
+    }
+    if plan.matched_players.is_empty() && plan.new_players.is_empty() {
+        println!("  {}", subtle("No scoreboard to match players from.", color_support));
+    }
+
+    println!("{}", header("Matches", color_support));
+    if plan.mode != MergeMode::AddedUp {
+        println!(
+            "  {} new, {} already here",
+            plan.new_matches.len(),
+            plan.duplicate_matches
+        );
+    }
+    let how = match plan.mode {
+        MergeMode::Replayed => "Stats and ratings recomputed by replaying both match logs.",
+        MergeMode::AddedOnTop => {
+            "This scoreboard has matches from before its match log - new matches are added on top."
+        }
+        MergeMode::AddedUp => {
+            "No match log - stats are added up, so don't import the same scoreboard twice."
+        }
+    };
+    println!("  {}", subtle(how, color_support));
+    if plan.missing_matches {
+        println!(
+            "  {}",
+            subtle(
+                "Their scoreboard counts matches missing from their match log - those stay behind.",
+                color_support
+            )
+        );
+    }
+
+    println!("{}", header("Changes", color_support));
+    let mut changes: Vec<(&str, String)> = plan
+        .scoreboard
+        .players
+        .iter()
+        .filter_map(|(id, after)| {
+            let before = local.players.get(id);
+            let change = describe_change(before, after)?;
+            Some((plan.scoreboard.display_name(id), change))
+        })
+        .collect();
+    changes.sort();
+
+    if changes.is_empty() {
+        println!("  {}", subtle("Nothing changes.", color_support));
+    }
+    for (name, change) in changes {
+        println!("  {} {change}", accent(&format!("{name:<20}"), color_support));
+    }
+}
+
+// "3 → 5 matches, 1 → 2 wins, Elo Classic 1216 → 1230" (None if nothing shown changed)
+fn describe_change(before: Option<&PlayerStats>, after: &PlayerStats) -> Option<String> {
+    let empty = PlayerStats::new();
+    let before = before.unwrap_or(&empty);
+
+    let mut parts = Vec::new();
+    let mut count = |label: &str, old: u32, new: u32| {
+        if old != new {
+            parts.push(format!("{old} → {new} {label}"));
+        }
+    };
+    count("matches", before.matches_played, after.matches_played);
+    count("wins", before.matches_won, after.matches_won);
+    count("rounds won", before.rounds_won, after.rounds_won);
+
+    for (ruleset, label) in [(Ruleset::Classic, "Classic"), (Ruleset::Extended, "Extended")] {
+        let old = before.ratings.get(&ruleset).map(|r| r.round());
+        let new = after.ratings.get(&ruleset).map(|r| r.round());
+        if old != new {
+            let show = |r: Option<f64>| r.map_or("-".to_string(), |r| format!("{r:.0}"));
+            parts.push(format!("Elo {label} {} → {}", show(old), show(new)));
+        }
+    }
+    if before.glicko.rating.round() != after.glicko.rating.round() {
+        parts.push(format!(
+            "Glicko-2 {:.0} → {:.0}",
+            before.glicko.rating, after.glicko.rating
+        ));
+    }
+
+    (!parts.is_empty()).then(|| parts.join(", "))
+}
+
+fn matches(count: usize) -> String {
+    if count == 1 {
+        "1 match".to_string()
+    } else {
+        format!("{count} matches")
+    }
+}
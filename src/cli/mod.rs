@@ -0,0 +1,146 @@
+pub mod admin;
+pub mod config;
+pub mod export;
+pub mod import;
+pub mod report;
+
+use chrono::{Local, NaiveDate, TimeZone, Utc};
+
+use crate::game::rules::Ruleset;
+use crate::history::query::MatchQuery;
+use crate::io::config::Config;
+use crate::io::storage::Storage;
+use crate::menu::main_menu::run_main_menu;
+use crate::menu::replay_menu::{run_replay_by_id, run_replays_menu};
+use crate::scoreboard::scoreboard::Scoreboard;
+
+const USAGE: &str = "Usage:
+  pl-project                              play (main menu)
+  pl-project config                       show the config file location and the settings in use
+  pl-project replays [MATCH_ID]           browse or open recorded matches
+  pl-project replays [FILTERS]            browse matches passing the filters
+  pl-project admin players                list player profiles
+  pl-project admin rename NAME NEW_NAME   rename a player (old name stays an alias)
+  pl-project admin merge FROM INTO        fold FROM's profile and stats into INTO
+  pl-project import PATH [--dry-run]      merge another installation's saves (data dir,
+                                          scoreboard.json or match_log.jsonl)
+  pl-project report [--output FILE]       league report as one HTML page (default league-report.html)
+  pl-project export scoreboard [OPTIONS] [FILTERS]
+                                          standings with win rates and ratings
+  pl-project export matches [OPTIONS] [FILTERS]
+                                          recorded match history
+
+Export options:
+  --format csv|json|markdown              default: from --output's extension, else markdown
+  --output FILE                           write to FILE instead of the terminal
+
+Filters:
+  --player NAME                           matches NAME played in
+  --ruleset classic|extended
+  --from YYYY-MM-DD / --to YYYY-MM-DD     played between these days (inclusive)";
+
+/* Dispatches the command line >> process exit code */
+pub fn run(args: &[&str], storage: &dyn Storage, config: &Config) -> i32 {
+    let color_support = config.color_support();
+    let result = match args {
+        [] => {
+            run_main_menu(storage, config);
+            Ok(())
+        }
+        ["config", rest @ ..] => match config::run_config(rest, config, color_support) {
+            Some(result) => result,
+            None => return usage(),
+        },
+        ["replays", id] if !id.starts_with("--") => run_replay_by_id(storage, id, color_support),
+        ["replays", filters @ ..] => parse_match_filters(storage, filters).map(|query| {
+            run_replays_menu(storage, &query, color_support);
+        }),
+        ["admin", rest @ ..] => match admin::run_admin(rest, storage, color_support) {
+            Some(result) => result,
+            None => return usage(),
+        },
+        ["import", rest @ ..] => match import::run_import(rest, storage, color_support) {
+            Some(result) => result,
+            None => return usage(),
+        },
+        ["report", rest @ ..] => match report::run_report(rest, storage) {
+            Some(result) => result,
+            None => return usage(),
+        },
+        ["export", rest @ ..] => match export::run_export(rest, storage) {
+            Some(result) => result,
+            None => return usage(),
+        },
+        _ => return usage(),
+    };
+
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("{e}");
+            1
+        }
+    }
+}
+
+fn usage() -> i32 {
+    eprintln!("{USAGE}");
+    2
+}
+
+/* --player / --ruleset / --from / --to >> MatchQuery (dates are local calendar days) */
+fn parse_match_filters(storage: &dyn Storage, args: &[&str]) -> Result<MatchQuery, String> {
+    let mut query = MatchQuery::default();
+    let mut args = args.iter();
+
+    while let Some(flag) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| format!("{flag} needs a value"))?;
+
+        match *flag {
+            "--player" => {
+                let scoreboard = Scoreboard::load(storage);
+                let profile = scoreboard
+                    .find_profile(value)
+                    .ok_or_else(|| format!("No player named '{value}'"))?;
+                query.player_id = Some(profile.id.clone());
+                query.player_names = std::iter::once(&profile.display_name)
+                    .chain(&profile.aliases)
+                    .cloned()
+                    .collect();
+            }
+            "--ruleset" => {
+                query.ruleset = Some(match value.to_lowercase().as_str() {
+                    "classic" => Ruleset::Classic,
+                    "extended" => Ruleset::Extended,
+                    _ => return Err(format!("Unknown ruleset '{value}' (classic or extended)")),
+                })
+            }
+            "--from" => query.from = Some(start_of_day(parse_date(value)?)?),
+            "--to" => {
+                let next_day = parse_date(value)?
+                    .succ_opt()
+                    .ok_or_else(|| format!("Date out of range: {value}"))?;
+                query.until = Some(start_of_day(next_day)?);
+            }
+            other => return Err(format!("Unknown filter '{other}'")),
+        }
+    }
+
+    Ok(query)
+}
+
+fn parse_date(text: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(text, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date '{text}' (use YYYY-MM-DD)"))
+}
+
+fn start_of_day(date: NaiveDate) -> Result<chrono::DateTime<Utc>, String> {
+    let midnight = date.and_hms_opt(0, 0, 0).unwrap_or_default();
+    Local
+        .from_local_datetime(&midnight)
+        .earliest()
+        .map(|at| at.with_timezone(&Utc))
+        .ok_or_else(|| format!("No local midnight on {date}"))
+}
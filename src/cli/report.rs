@@ -0,0 +1,28 @@
+use chrono::Utc;
+
+use crate::io::file_manager::write_atomic;
+use crate::io::storage::Storage;
+use crate::report::league_report;
+use crate::scoreboard::scoreboard::Scoreboard;
+
+const DEFAULT_OUTPUT: &str = "league-report.html";
+
+/* `report [--output FILE]` >> None when that doesn't parse */
+pub fn run_report(args: &[&str], storage: &dyn Storage) -> Option<Result<(), String>> {
+    let output = match args {
+        [] => DEFAULT_OUTPUT,
+        ["--output", file] => file,
+        _ => return None,
+    };
+    Some(report(output, storage))
+}
+
+fn report(output: &str, storage: &dyn Storage) -> Result<(), String> {
+    let scoreboard = Scoreboard::load(storage);
+    let records = storage.load_matches()?;
+
+    let html = league_report(&scoreboard, &records, Utc::now());
+    write_atomic(output, &html).map_err(|e| format!("Write report failed: {e}"))?;
+    println!("Report written to {output}");
+    Ok(())
+}
@@ -3,14 +3,27 @@ use crate::display::ascii::{
     move_selection_banner,
 };
 
+use crate::achievements::achievement::Achievement;
+use crate::achievements::tracker::Unlock;
 use crate::display::colors::{accent, header, info, subtle, success, failure, ColorSupport};
 
 use crate::game::game_state::GameState;
+use crate::game::match_settings::TimeoutPenalty;
 use crate::game::round::RoundResult;
-use crate::game::rules::Ruleset;    
+use crate::game::rules::{Gesture, Ruleset};
+use crate::history::match_record::MatchRecord;
+use crate::io::save_load::SaveSlot;
+use crate::players::profile::{ColorTheme, PlayerProfile};
 use crate::scoreboard::scoreboard::Scoreboard;
-use crate::scoreboard::stats::PlayerStats;
-use crate::utils::input::{read_input, read_number};
+use crate::scoreboard::records::AllTimeRecords;
+use crate::scoreboard::stats::{HeadToHead, PlayerStats};
+use crate::scoreboard::tendencies::frequencies;
+use crate::utils::input::{
+    discard_pending_input, parse_gesture, read_input, read_input_until, read_number,
+};
+
+use chrono::Local;
+use std::time::{Duration, Instant};
 
 /* ROUND SUMMARY */
 pub fn show_round_summary(state: &GameState, result: &RoundResult, color_support: ColorSupport) {
@@ -33,6 +46,18 @@ pub fn show_round_summary(state: &GameState, result: &RoundResult, color_support
             p2.as_str(),
             ascii_gesture(p2)
         );
+
+        /* Blitz: how fast each throw was */
+        if let (Some(_), Some(round)) = (state.blitz, state.rounds.last()) {
+            println!(
+                "{} {} {}, {} {}",
+                subtle("Reaction:", color_support),
+                state.player1.name,
+                format_reaction(round.reaction_ms_p1),
+                state.player2.name,
+                format_reaction(round.reaction_ms_p2)
+            );
+        }
         println!();
     }
 
@@ -48,6 +73,15 @@ pub fn show_round_summary(state: &GameState, result: &RoundResult, color_support
         RoundResult::Tie => println!("{}", info(">> The round is a tie.", color_support)),
     }
 
+    if let Some(round) = state.rounds.last() {
+        if round.won_on_speed {
+            println!("{}", info("⚡ Same gesture — the faster throw takes it!", color_support));
+        }
+        if round.bonus_point {
+            println!("{}", info("⚡ Sub-second throw — bonus point!", color_support));
+        }
+    }
+
     println!();
     println!(
         "{}  {} {}  -  {} {}",
@@ -77,6 +111,7 @@ pub fn show_round_summary(state: &GameState, result: &RoundResult, color_support
 pub fn show_match_victory(
     state: &GameState,
     winner: Option<String>,
+    announcements: &[String],
     color_support: ColorSupport,
 ) {
     println!();
@@ -103,21 +138,19 @@ pub fn show_match_victory(
     );
 
     /* Player Analytics */
-    let total_rounds = state.history_p1.len() as u32;
+    let total_rounds = state.rounds.len();
     if total_rounds > 0 {
-        let ties = total_rounds
-            .saturating_sub(state.score_player1)
-            .saturating_sub(state.score_player2);
+        let count = |r: RoundResult| state.rounds.iter().filter(|x| x.result == r).count();
 
         println!(
             "{} total rounds = {}, {} won {}, {} won {}, ties {}",
             subtle("Summary:", color_support),
             total_rounds,
             state.player1.name,
-            state.score_player1,
+            count(RoundResult::Player1Win),
             state.player2.name,
-            state.score_player2,
-            ties
+            count(RoundResult::Player2Win),
+            count(RoundResult::Tie)
         );
 
         if let Some(most) = most_frequent(&state.history_p1) {
@@ -139,14 +172,50 @@ pub fn show_match_victory(
                 ascii_gesture(&most)
             );
         }
+
+        if state.blitz.is_some() {
+            let p1_times: Vec<u64> = state.rounds.iter().filter_map(|r| r.reaction_ms_p1).collect();
+            let p2_times: Vec<u64> = state.rounds.iter().filter_map(|r| r.reaction_ms_p2).collect();
+
+            println!(
+                "{} average reaction: {} {}, {} {}",
+                subtle("-", color_support),
+                state.player1.name,
+                format_reaction(average(&p1_times)),
+                state.player2.name,
+                format_reaction(average(&p2_times))
+            );
+        }
+    }
+
+    if !announcements.is_empty() {
+        println!();
+        for line in announcements {
+            println!("{}", success(line, color_support));
+        }
     }
 
     println!("{}", section_divider());
 }
 
+/* Reaction Time Helpers */
+fn average(times: &[u64]) -> Option<u64> {
+    if times.is_empty() {
+        None
+    } else {
+        Some(times.iter().sum::<u64>() / times.len() as u64)
+    }
+}
+
+fn format_reaction(ms: Option<u64>) -> String {
+    match ms {
+        Some(ms) => format!("{:.2}s", ms as f64 / 1000.0),
+        None => "—".to_string(),
+    }
+}
+
 /* Most Frequent Gesture Helper */
-fn most_frequent(history: &[crate::game::rules::Gesture]) -> Option<crate::game::rules::Gesture> {
-    use crate::game::rules::Gesture;
+fn most_frequent(history: &[Gesture]) -> Option<Gesture> {
     use std::collections::HashMap;
 
     let mut counts: HashMap<Gesture, u32> = HashMap::new();
@@ -159,17 +228,20 @@ fn most_frequent(history: &[crate::game::rules::Gesture]) -> Option<crate::game:
 }   
 
 /* MAIN MENU */
-pub fn show_main_menu() {
+pub fn show_main_menu(color_support: ColorSupport) {
     println!("{}", main_menu_banner());
     println!("{}", section_divider());
-    println!("{}", header(" MAIN MENU ", ColorSupport::Enabled));
+    println!("{}", header(" MAIN MENU ", color_support));
     println!("{}", section_divider());
     println!();
 
-    println!("{} {}", accent("1)", ColorSupport::Enabled), "Start New Game");
-    println!("{} {}", accent("2)", ColorSupport::Enabled), "Continue Saved Game");
-    println!("{} {}", accent("3)", ColorSupport::Enabled), "View Scoreboard");
-    println!("{} {}", accent("4)", ColorSupport::Enabled), "Exit");
+    println!("{} Start New Game", accent("1)", color_support));
+    println!("{} Continue Saved Game", accent("2)", color_support));
+    println!("{} View Scoreboard", accent("3)", color_support));
+    println!("{} Replays", accent("4)", color_support));
+    println!("{} Achievements", accent("5)", color_support));
+    println!("{} Profiles", accent("6)", color_support));
+    println!("{} Exit", accent("7)", color_support));
 }
 
 pub fn ask_main_menu_choice() -> u32 {
@@ -177,21 +249,46 @@ pub fn ask_main_menu_choice() -> u32 {
     read_number("Enter choice: ")
 }
 
+/* TAMPER WARNING */
+/* `what` changed outside the game ("The scoreboard was"), and whose data it touched */
+pub fn show_tamper_warning(what: &str, players: &[&str], color: ColorSupport) {
+    println!();
+    println!("{}", failure(&format!("⚠ {what} changed outside the game."), color));
+    if players.is_empty() {
+        println!("{}", subtle("The change couldn't be pinned to a player.", color));
+    } else {
+        println!("Edited for: {}", players.join(", "));
+        println!("{}", subtle("Keeping the edits marks them with ⚠ on the leaderboards for good.", color));
+    }
+}
+
+/* Name column with the ⚠ marker for stats edited outside the game */
+pub fn marked(name: &str, stats: &PlayerStats) -> String {
+    if stats.tampered {
+        format!("{name} ⚠")
+    } else {
+        name.to_string()
+    }
+}
+
 /* SCOREBOARD */
-pub fn show_scoreboard(scoreboard: &Scoreboard) {
+pub fn show_scoreboard(scoreboard: &Scoreboard, color_support: ColorSupport) {
     println!();
     println!("{}", section_divider());
-    println!("{}", header(" SCOREBOARD ", ColorSupport::Enabled));
+    println!("{}", header(" SCOREBOARD ", color_support));
     println!("{}", section_divider());
 
     let sorted = scoreboard.sorted_by_wins();
 
     if sorted.is_empty() {
-        println!("{}", subtle("No player statistics yet.", ColorSupport::Enabled));
+        println!("{}", subtle("No player statistics yet.", color_support));
         return;
     }
 
-    println!("{:<20} {:>10} {:>10} {:>10}", "Player", "Matches", "Wins", "Win %");
+    println!(
+        "{:<20} {:>10} {:>10} {:>10} {:>10} {:>8} {:>6}",
+        "Player", "Matches", "Wins", "Win %", "Avg Time", "Streak", "Best"
+    );
     println!("{}", section_divider());
 
     for (name, stats) in sorted {
@@ -206,21 +303,489 @@ fn print_player_line(name: &str, stats: &PlayerStats) {
         0.0
     };
 
+    let streak = if stats.streaks.current_wins > 0 {
+        format!("W{}", stats.streaks.current_wins)
+    } else if stats.streaks.current_losses > 0 {
+        format!("L{}", stats.streaks.current_losses)
+    } else {
+        "—".to_string()
+    };
+
+    println!(
+        "{:<20} {:>10} {:>10} {:>9.2}% {:>10} {:>8} {:>6}",
+        marked(name, stats),
+        stats.matches_played,
+        stats.matches_won,
+        win_rate,
+        format_reaction(stats.average_reaction_ms()),
+        streak,
+        format!("W{}", stats.streaks.best_wins)
+    );
+}
+
+/* ALL-TIME RECORDS */
+pub fn show_all_time_records(records: &AllTimeRecords, color: ColorSupport) {
+    println!();
+    println!("{}", section_divider());
+    println!("{}", header(" 🏆 ALL-TIME RECORDS ", color));
+    println!("{}", section_divider());
+
+    for (label, entry, unit) in [
+        ("Longest match:   ", &records.longest_match, "rounds"),
+        ("Longest tie chain:", &records.longest_tie_chain, "ties in a row"),
+        ("Biggest comeback:", &records.biggest_comeback, "points down"),
+    ] {
+        match entry {
+            Some(r) => println!(
+                "{} {} {}{}  {} {} vs {}, {}",
+                accent(label, color),
+                r.value,
+                unit,
+                r.holder.as_ref().map(|h| format!(" — {h}")).unwrap_or_default(),
+                subtle("|", color),
+                r.player1,
+                r.player2,
+                r.played_at.with_timezone(&Local).format("%Y-%m-%d")
+            ),
+            None => println!("{} {}", accent(label, color), subtle("not set yet", color)),
+        }
+    }
+}
+
+/* STREAK LEADERS */
+pub fn show_streak_leaders(scoreboard: &Scoreboard, color: ColorSupport) {
+    let leader = |key: fn(&PlayerStats) -> u32| {
+        scoreboard
+            .players
+            .iter()
+            .max_by_key(|(_, s)| key(s))
+            .filter(|(_, s)| key(s) > 0)
+            .map(|(id, s)| format!("{} ({})", scoreboard.display_name(id), key(s)))
+            .unwrap_or_else(|| "—".to_string())
+    };
+
+    println!();
+    println!("{} {}", accent("Best win streak:       ", color), leader(|s| s.streaks.best_wins));
+    println!("{} {}", accent("Worst losing streak:   ", color), leader(|s| s.streaks.best_losses));
+    println!("{} {}", accent("Most rounds won in row:", color), leader(|s| s.streaks.best_round_wins));
+}
+
+/* RATING LEADERBOARD */
+pub fn show_rating_leaderboard(scoreboard: &Scoreboard, ruleset: &Ruleset, color_support: ColorSupport) {
+    println!();
+    println!("{}", section_divider());
+    println!(
+        "{}",
+        header(&format!(" {:?} RATINGS ", ruleset).to_uppercase(), color_support)
+    );
+    println!("{}", section_divider());
+
+    let sorted = scoreboard.sorted_by_rating(ruleset);
+
+    if sorted.is_empty() {
+        println!("{}", subtle("No rated matches in this ruleset yet.", color_support));
+        return;
+    }
+
+    println!("{:<20} {:>10} {:>10} {:>10}", "Player", "Rating", "Matches", "Wins");
+    println!("{}", section_divider());
+
+    for (name, stats) in sorted {
+        println!(
+            "{:<20} {:>10.0} {:>10} {:>10}",
+            marked(name, stats),
+            stats.rating(ruleset),
+            stats.matches_played,
+            stats.matches_won
+        );
+    }
+}
+
+/* GLICKO-2 LEADERBOARD */
+pub fn show_glicko_leaderboard(scoreboard: &Scoreboard, hide_provisional: bool, color_support: ColorSupport) {
+    println!();
+    println!("{}", section_divider());
+    println!("{}", header(" GLICKO-2 RATINGS ", color_support));
+    println!("{}", section_divider());
+
+    let sorted = scoreboard.sorted_by_glicko(hide_provisional);
+
+    if sorted.is_empty() {
+        println!("{}", subtle("No rated players yet.", color_support));
+    } else {
+        println!("{:<20} {:>8} {:>8} {:>10}", "Player", "Rating", "RD", "Status");
+        println!("{}", section_divider());
+
+        for (name, stats) in sorted {
+            let status = if stats.glicko.is_provisional() { "provisional" } else { "" };
+            println!(
+                "{:<20} {:>8.0} {:>8.0} {:>10}",
+                marked(name, stats), stats.glicko.rating, stats.glicko.deviation, status
+            );
+        }
+    }
+
+    let pending = scoreboard.rating_periods.pending.len();
+    if pending > 0 {
+        println!();
+        println!(
+            "{}",
+            subtle(
+                &format!(
+                    "{pending} match(es) will be rated when the current {}-day period ends.",
+                    scoreboard.rating_periods.period_days
+                ),
+                color_support
+            )
+        );
+    }
+}
+
+/* PLAYER PICKER */
+pub fn show_player_picker(title: &str, names: &[&str], color: ColorSupport) {
+    println!();
+    println!("{}", header(&format!(" {} ", title.to_uppercase()), color));
+    println!("{}", section_divider());
+
+    if names.is_empty() {
+        println!("{}", subtle("No player statistics yet.", color));
+        return;
+    }
+
+    for (i, name) in names.iter().enumerate() {
+        println!("{} {}", accent(&format!("{:>3})", i + 1), color), name);
+    }
+}
+
+/* PROFILE PICKER - existing profiles with rating and last match, then "new profile" */
+pub fn show_profile_picker(title: &str, profiles: &[(&PlayerProfile, &PlayerStats)], color: ColorSupport) {
+    println!();
+    println!("{}", section_divider());
+    println!("{}", header(&format!(" {title} "), color));
+    println!("{}", section_divider());
+
+    if !profiles.is_empty() {
+        println!("     {:<20} {:>8} {:>12}", "Player", "Rating", "Last played");
+    }
+
+    for (i, (profile, stats)) in profiles.iter().enumerate() {
+        let rating = format!(
+            "{:.0}{}",
+            stats.glicko.rating,
+            if stats.glicko.is_provisional() { "?" } else { "" }
+        );
+        let last_played = profile
+            .last_played
+            .map(|at| at.with_timezone(&Local).format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| "—".to_string());
+
+        println!(
+            "{} {:<20} {:>8} {:>12}",
+            accent(&format!("{:>3})", i + 1), color),
+            profile.display_name,
+            rating,
+            subtle(&last_played, color)
+        );
+    }
+
+    println!("{} ➕ New profile", accent(&format!("{:>3})", profiles.len() + 1), color));
+}
+
+/* PROFILE PREFERENCES */
+pub fn show_preferences(profile: &PlayerProfile, color: ColorSupport) {
+    let prefs = &profile.preferences;
+
+    println!();
+    println!("{}", section_divider());
+    println!("{}", header(&format!(" ⚙️  PREFERENCES — {} ", profile.display_name), color));
+    println!("{}", section_divider());
+    println!(
+        "{} Default ruleset: {}",
+        accent("1)", color),
+        prefs.default_ruleset.as_ref().map_or("ask every time".to_string(), |r| format!("{r:?}"))
+    );
+    println!(
+        "{} Colour theme:    {}",
+        accent("2)", color),
+        match prefs.color_theme {
+            ColorTheme::Neon => "Neon",
+            ColorTheme::Plain => "Plain (no colours)",
+        }
+    );
+    println!(
+        "{} Favourite AI:    {}",
+        accent("3)", color),
+        prefs.favourite_ai.as_ref().map_or("ask every time".to_string(), |d| format!("{d:?}"))
+    );
+}
+
+/* HEAD-TO-HEAD */
+pub fn show_head_to_head(
+    player: &str,
+    opponent: &str,
+    record: Option<&HeadToHead>,
+    opponent_record: Option<&HeadToHead>,
+    pairings: &[((Gesture, Gesture), u32)],
+    color: ColorSupport,
+) {
+    println!();
+    println!("{}", section_divider());
+    println!("{}", header(&format!(" ⚔️  {player} vs {opponent} "), color));
+    println!("{}", section_divider());
+
+    let Some(h2h) = record else {
+        println!("{}", subtle("These two have never played each other.", color));
+        return;
+    };
+
+    let ties = h2h.matches_played - h2h.matches_won - h2h.matches_lost;
+    println!(
+        "{} {} {}  -  {} {}  (ties {})",
+        accent("Matches:", color),
+        player,
+        h2h.matches_won,
+        h2h.matches_lost,
+        opponent,
+        ties
+    );
+    println!(
+        "{} {} {}  -  {} {}  (ties {})",
+        accent("Rounds: ", color),
+        player,
+        h2h.rounds_won,
+        h2h.rounds_lost,
+        opponent,
+        h2h.rounds_tied
+    );
+    let (their_longest, their_current) = opponent_record
+        .map(|r| (r.longest_streak, r.current_streak))
+        .unwrap_or((0, 0));
     println!(
-        "{:<20} {:>10} {:>10} {:>9.2}%",
-        name, stats.matches_played, stats.matches_won, win_rate
+        "{} {} {} (now {}),  {} {} (now {})",
+        accent("Longest win streak:", color),
+        player,
+        h2h.longest_streak,
+        h2h.current_streak,
+        opponent,
+        their_longest,
+        their_current
     );
+
+    if !pairings.is_empty() {
+        println!();
+        println!("{}", info("Most common pairings:", color));
+        for ((mine, theirs), count) in pairings.iter().take(5) {
+            println!(
+                "{} {} {} vs {} {}  ×{}",
+                subtle("-", color),
+                mine.as_str(),
+                ascii_gesture(mine),
+                theirs.as_str(),
+                ascii_gesture(theirs),
+                count
+            );
+        }
+    }
 }
 
-pub fn show_scoreboard_sort_menu() -> u32 {
+/* PLAYER PROFILE - lifetime tendencies */
+pub fn show_player_profile(name: &str, stats: &PlayerStats, color: ColorSupport) {
     println!();
-    println!("{}", header(" SORT SCOREBOARD ", ColorSupport::Enabled));
     println!("{}", section_divider());
-    println!("{} {}", accent("1)", ColorSupport::Enabled), "Matches won");
-    println!("{} {}", accent("2)", ColorSupport::Enabled), "Win rate");
+    println!("{}", header(&format!(" 👤 PLAYER PROFILE — {name} "), color));
+    println!("{}", section_divider());
+
+    print_player_line(name, stats);
+
+    let tendencies = &stats.tendencies;
+    let total = tendencies.total();
+    if total == 0 {
+        println!();
+        println!("{}", subtle("No recorded gestures yet.", color));
+        return;
+    }
+
+    println!();
+    println!("{} {}", info("Gestures thrown:", color), total);
+    for (gesture, share) in frequencies(&tendencies.counts) {
+        let filled = (share * 20.0).round() as usize;
+        println!(
+            "   {:<9} {}{} {:>5.1}%",
+            gesture.as_str(),
+            "█".repeat(filled),
+            "░".repeat(20 - filled),
+            share * 100.0
+        );
+    }
+
+    println!();
+    for (label, counts) in [
+        ("After a win: ", &tendencies.after_win),
+        ("After a loss:", &tendencies.after_loss),
+        ("After a tie: ", &tendencies.after_tie),
+    ] {
+        match frequencies(counts).first() {
+            Some((gesture, share)) => println!(
+                "{} mostly {} ({:.0}%)",
+                accent(label, color),
+                gesture.as_str(),
+                share * 100.0
+            ),
+            None => println!("{} {}", accent(label, color), subtle("not enough data", color)),
+        }
+    }
+
+    println!();
+    println!(
+        "{} {:.2} bits",
+        accent("Entropy:", color),
+        tendencies.entropy()
+    );
+    if let Some(predictability) = tendencies.predictability() {
+        println!(
+            "{} {:.0}%  {}",
+            accent("Predictability:", color),
+            predictability * 100.0,
+            subtle("(0% = perfectly random, 100% = always the same gesture)", color)
+        );
+    }
+}
+
+pub fn show_scoreboard_sort_menu(color_support: ColorSupport) -> u32 {
+    println!();
+    println!("{}", header(" SORT SCOREBOARD ", color_support));
+    println!("{}", section_divider());
+    println!("{} Matches won", accent("1)", color_support));
+    println!("{} Win rate", accent("2)", color_support));
+    println!("{} Rating (Classic)", accent("3)", color_support));
+    println!("{} Rating (Extended)", accent("4)", color_support));
+    println!("{} Glicko-2 rating", accent("5)", color_support));
+    println!("{} Head-to-head", accent("6)", color_support));
+    println!("{} Player profile", accent("7)", color_support));
+    println!("{} Streaks & all-time records", accent("8)", color_support));
     read_number("Enter choice: ")
 }
 
+/* REPLAYS */
+pub fn show_replay_list(records: &[MatchRecord], color: ColorSupport) {
+    println!();
+    println!("{}", section_divider());
+    println!("{}", header(" REPLAYS ", color));
+    println!("{}", section_divider());
+
+    if records.is_empty() {
+        println!("{}", subtle("No recorded matches yet.", color));
+        return;
+    }
+
+    for (i, record) in records.iter().enumerate() {
+        println!(
+            "{} {}  {} {} - {} {}  {:?}  {}",
+            accent(&format!("{:>3})", i + 1), color),
+            record.played_at.with_timezone(&Local).format("%Y-%m-%d %H:%M"),
+            record.player1.name,
+            record.score_player1,
+            record.score_player2,
+            record.player2.name,
+            record.ruleset,
+            subtle(&record.id, color)
+        );
+    }
+}
+
+/* SAVED GAMES (Continue) */
+pub fn show_save_slots(slots: &[SaveSlot], color: ColorSupport) {
+    println!();
+    println!("{}", section_divider());
+    println!("{}", header(" 💾 SAVED GAMES ", color));
+    println!("{}", section_divider());
+
+    if slots.is_empty() {
+        println!("{}", subtle("No saved games.", color));
+        return;
+    }
+
+    for (i, slot) in slots.iter().enumerate() {
+        let saved_at = slot.saved_at.map_or_else(
+            || "saved earlier".to_string(),
+            |at| at.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string(),
+        );
+        let name = if slot.tampered { format!("{} ⚠", slot.name) } else { slot.name.clone() };
+        println!(
+            "{} {:<24} {} {} - {} {}  {:?}  round {}  {}",
+            accent(&format!("{:>3})", i + 1), color),
+            name,
+            slot.player1,
+            slot.score_player1,
+            slot.score_player2,
+            slot.player2,
+            slot.ruleset,
+            slot.round,
+            subtle(&saved_at, color)
+        );
+    }
+}
+
+pub fn show_replay_header(record: &MatchRecord, step: usize, color: ColorSupport) {
+    let total = record.rounds.len();
+    let position = if step <= total {
+        format!("Round {step}/{total}")
+    } else {
+        "Final".to_string()
+    };
+
+    println!();
+    println!("============================================");
+    println!(
+        "     📼 REPLAY — {} vs {} — {}",
+        record.player1.name, record.player2.name, position
+    );
+    println!("============================================");
+    println!(
+        "{} {}  {:?}  {:?}",
+        subtle("Played:", color),
+        record.played_at.with_timezone(&Local).format("%Y-%m-%d %H:%M"),
+        record.ruleset,
+        record.match_format
+    );
+}
+
+/* ACHIEVEMENTS */
+pub fn show_achievements(player: &str, unlocks: &[Unlock], color: ColorSupport) {
+    println!();
+    println!("{}", section_divider());
+    println!("{}", header(&format!(" 🏅 ACHIEVEMENTS — {player} "), color));
+    println!("{}", section_divider());
+
+    for achievement in Achievement::ALL {
+        match unlocks.iter().find(|u| u.achievement == achievement) {
+            Some(unlock) => println!(
+                "{} {:<16} {}  {}",
+                success("✔", color),
+                achievement.title(),
+                achievement.description(),
+                subtle(
+                    &unlock.unlocked_at.with_timezone(&Local).format("%Y-%m-%d").to_string(),
+                    color
+                )
+            ),
+            None => println!(
+                "{} {}",
+                subtle("·", color),
+                subtle(&format!("{:<16} {}", achievement.title(), achievement.description()), color)
+            ),
+        }
+    }
+
+    println!();
+    println!(
+        "{} {}/{}",
+        accent("Unlocked:", color),
+        unlocks.len(),
+        Achievement::ALL.len()
+    );
+}
+
 /* SAVE PROMPT */
 pub fn show_save_prompt() -> bool {
     println!();
@@ -229,22 +794,28 @@ pub fn show_save_prompt() -> bool {
 }
 
 /* END OF MATCH ACTIONS */
-pub fn show_match_actions_menu() -> u32 {
+pub fn show_match_actions_menu(color_support: ColorSupport) -> u32 {
     println!();
-    println!("{}", header(" MATCH COMPLETE ", ColorSupport::Enabled));
+    println!("{}", header(" MATCH COMPLETE ", color_support));
     println!("{}", section_divider());
 
-    println!("{} {}", accent("1)", ColorSupport::Enabled), "Rematch with same settings");
-    println!("{} {}", accent("2)", ColorSupport::Enabled), "Change ruleset/format");
-    println!("{} {}", accent("3)", ColorSupport::Enabled), "Return to main menu");
+    println!("{} Rematch with same settings", accent("1)", color_support));
+    println!("{} Change ruleset/format", accent("2)", color_support));
+    println!("{} Return to main menu", accent("3)", color_support));
 
     read_number("Enter choice: ")
 }
 
 
-/* NEON ARCADE MOVE PICKER - DYNAMIC BASED ON RULESET */
-
-pub fn show_move_prompt(player_name: &str, ruleset: &Ruleset, color: ColorSupport) -> String {
+/* NEON ARCADE MOVE PICKER - DYNAMIC BASED ON RULESET
+ * With a time limit the prompt returns None once the countdown runs out.
+*/
+pub fn show_move_prompt(
+    player_name: &str,
+    ruleset: &Ruleset,
+    color: ColorSupport,
+    time_limit: Option<u32>,
+) -> Option<Gesture> {
     println!("\n{}", move_selection_banner());
     println!("{} {}", accent("PLAYER:", color), player_name);
     
@@ -260,16 +831,40 @@ pub fn show_move_prompt(player_name: &str, ruleset: &Ruleset, color: ColorSuppor
     }
     println!();
 
+    let deadline = time_limit.map(|secs| {
+        println!(
+            "{}",
+            info(&format!("⏱  You have {secs} seconds to choose!"), color)
+        );
+        discard_pending_input();
+        Instant::now() + Duration::from_secs(secs as u64)
+    });
+
     loop {
-        let input = read_input(">> ").to_lowercase();
-
-        match input.as_str() {
-            "1" | "rock" | "r" => return "rock".into(),
-            "2" | "paper" | "p" => return "paper".into(),
-            "3" | "scissors" | "s" => return "scissors".into(),
-            "4" | "lizard" | "l" if matches!(ruleset, Ruleset::Extended) => return "lizard".into(),
-            "5" | "spock" | "k" if matches!(ruleset, Ruleset::Extended) => return "spock".into(),
-            _ => {
+        let input = match deadline {
+            Some(deadline) => match read_input_until(">> ", deadline) {
+                Some(input) => input,
+                None => {
+                    println!();
+                    println!("{}", failure("⏱  Time's up!", color));
+                    return None;
+                }
+            },
+            None => read_input(">> "),
+        };
+
+        let gesture = match input.as_str() {
+            "1" => Some(Gesture::Rock),
+            "2" => Some(Gesture::Paper),
+            "3" => Some(Gesture::Scissors),
+            "4" if matches!(ruleset, Ruleset::Extended) => Some(Gesture::Lizard),
+            "5" if matches!(ruleset, Ruleset::Extended) => Some(Gesture::Spock),
+            other => parse_gesture(other, ruleset),
+        };
+
+        match gesture {
+            Some(g) => return Some(g),
+            None => {
                 let hint = if matches!(ruleset, Ruleset::Extended) {
                     "Invalid input. Use 1-5 or r/p/s/l/k."
                 } else {
@@ -279,4 +874,16 @@ pub fn show_move_prompt(player_name: &str, ruleset: &Ruleset, color: ColorSuppor
             }
         }
     }
-}
\ No newline at end of file
+}
+
+/* TIMEOUT PENALTY NOTICE */
+pub fn show_timeout_notice(player_name: &str, penalty: TimeoutPenalty, color: ColorSupport) {
+    let text = match penalty {
+        TimeoutPenalty::RandomGesture => {
+            format!("{player_name} ran out of time — a random gesture was thrown.")
+        }
+        TimeoutPenalty::ForfeitRound => format!("{player_name} ran out of time and forfeits the round."),
+        TimeoutPenalty::ForfeitMatch => format!("{player_name} ran out of time and forfeits the match."),
+    };
+    println!("{}", failure(&text, color));
+}
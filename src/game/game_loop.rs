@@ -1,23 +1,32 @@
+use crate::achievements::tracker::AchievementBook;
 use crate::display::colors::ColorSupport;
 use crate::display::ui::{
     show_match_actions_menu, show_match_victory, show_round_summary, show_save_prompt,
-    show_move_prompt,
+    show_move_prompt, show_timeout_notice,
 };
 use crate::game::game_state::GameState;
-use crate::game::round::{resolve_round, RoundResult};
-use crate::players::ai::generate_ai_move;
-use crate::players::player::PlayerType;
+use crate::game::match_settings::TimeoutPenalty;
+use crate::game::round::{forfeit_round, resolve_round, RoundResult, Throw};
+use crate::players::ai::{ai_reaction_ms, generate_ai_move};
+use crate::players::ai_difficulty::AiDifficulty;
+use crate::players::player::{Player, PlayerType};
 use crate::scoreboard::scoreboard::Scoreboard;
 use crate::scoreboard::stats::MatchOutcome;
 use crate::utils::clear_screen::clear_screen;
+use crate::history::match_record::MatchRecord;
 use crate::io::save_load::{save_game_state_and_scoreboard, delete_save_state};
+use crate::io::storage::Storage;
+use crate::menu::save_menu::choose_slot_name;
 
 use crate::game::rules::Gesture;
 
+use std::time::Instant;
+
 /* Runs a FULL MATCH Untill Someone Wins */
 pub fn run_match(
     mut state: GameState,
     mut scoreboard: Scoreboard,
+    storage: &dyn Storage,
     color_support: ColorSupport,
 ) -> (Scoreboard, Option<GameState>) {
     let required_wins = state.match_format.required_wins();
@@ -42,14 +51,41 @@ pub fn run_match(
         println!("First to {} wins\n", required_wins);
 
         /* MOVE COLLECTION WITH ULTRA ARCADE UI */
-        let (g1, g2) = get_round_moves(&state, color_support);
-
-        let result = resolve_round(&mut state, g1, g2);
-        show_round_summary(&state, &result, color_support);
+        let forfeited_match = match get_round_moves(&state, color_support) {
+            RoundMoves::Played(t1, t2) => {
+                let result = resolve_round(&mut state, t1, t2);
+                show_round_summary(&state, &result, color_support);
+                None
+            }
+            RoundMoves::TimedOut(awarded) => {
+                let penalty = timeout_penalty(&state);
+                let late_player = match awarded {
+                    RoundResult::Player1Win => &state.player2.name,
+                    _ => &state.player1.name,
+                };
+                show_timeout_notice(late_player, penalty, color_support);
+
+                if penalty == TimeoutPenalty::ForfeitMatch {
+                    Some(awarded)
+                } else {
+                    let result = forfeit_round(&mut state, awarded);
+                    show_round_summary(&state, &result, color_support);
+                    None
+                }
+            }
+        };
 
         /* Victory condition */
-        if state.score_player1 >= required_wins || state.score_player2 >= required_wins {
-            let winner = if state.score_player1 > state.score_player2 {
+        if forfeited_match.is_some()
+            || state.score_player1 >= required_wins
+            || state.score_player2 >= required_wins
+        {
+            let winner = if let Some(awarded) = forfeited_match {
+                match awarded {
+                    RoundResult::Player1Win => Some(state.player1.name.clone()),
+                    _ => Some(state.player2.name.clone()),
+                }
+            } else if state.score_player1 > state.score_player2 {
                 Some(state.player1.name.clone())
             } else if state.score_player2 > state.score_player1 {
                 Some(state.player2.name.clone())
@@ -57,26 +93,50 @@ pub fn run_match(
                 None
             };
 
-            show_match_victory(&state, winner.clone(), color_support);
+            let outcome = match winner {
+                Some(ref w) if w == &state.player1.name => MatchOutcome::Player1Win,
+                Some(ref w) if w == &state.player2.name => MatchOutcome::Player2Win,
+                _ => MatchOutcome::Tie,
+            };
+            // Stamp the scoreboard profiles on the players so the record keeps them
+            state.player1.profile_id = Some(scoreboard.ensure_player(&state.player1));
+            state.player2.profile_id = Some(scoreboard.ensure_player(&state.player2));
+            let record = MatchRecord::from_state(&state, outcome);
+
+            /* Match Log + Scoreboard Update */
+            if let Err(e) = storage.append_match(&record) {
+                println!("Warning: {e}");
+            }
+            let broken_records = scoreboard.update_after_match(&record);
+            let mut announcements = scoreboard.match_announcements(&record, &broken_records);
+
+            /* Achievements */
+            let mut achievements = AchievementBook::load(storage, &scoreboard);
+            for (player, achievement) in achievements.unlock_from_match(&record, &scoreboard) {
+                announcements.push(format!(
+                    "🏅 {player} unlocked \"{}\" — {}",
+                    achievement.title(),
+                    achievement.description()
+                ));
+            }
+            if let Err(e) = achievements.save(storage) {
+                println!("Warning: {e}");
+            }
+
+            show_match_victory(&state, winner, &announcements, color_support);
 
-            /* Scoreboard Update */
-            scoreboard.update_after_match(
-                &state.player1.name,
-                &state.player2.name,
-                state.score_player1,
-                state.score_player2,
-                match winner {
-                    Some(ref w) if w == &state.player1.name => MatchOutcome::Player1Win,
-                    Some(ref w) if w == &state.player2.name => MatchOutcome::Player2Win,
-                    _ => MatchOutcome::Tie,
-                },
-            );
+            let _ = scoreboard.save(storage);
 
-            let _ = scoreboard.save();
-            delete_save_state();
+            // The match is over, so its save slot (if it had one) goes - other saves stay
+            if let Some(slot) = state.save_slot.take()
+                && let Err(e) = delete_save_state(storage, &slot)
+            {
+                println!("Warning: {e}");
+            }
+            state.saved_at = None;
 
             /* End Of Match MENU */
-            match show_match_actions_menu() {
+            match show_match_actions_menu(color_support) {
                 1 => {
                     state.reset_for_rematch();
                     continue;
@@ -89,30 +149,46 @@ pub fn run_match(
 
         /* Ask if player wants to save & exit */
         if show_save_prompt() {
-            let _ = save_game_state_and_scoreboard(&state, &scoreboard);
-            println!("Game saved. Returning to menu...");
-            return (scoreboard, Some(state));
+            if state.save_slot.is_none() {
+                state.save_slot = Some(choose_slot_name(storage, &state, color_support));
+            }
+            match save_game_state_and_scoreboard(storage, &mut state, &scoreboard) {
+                Ok(()) => {
+                    println!("Game saved. Returning to menu...");
+                    return (scoreboard, Some(state));
+                }
+                // Keep playing rather than lose the match
+                Err(e) => println!("Could not save the game: {e}"),
+            }
         }
 
         state.current_round += 1;
     }
 }
 
-/* Collects moves for the round */ 
-fn get_round_moves(state: &GameState, color_support: ColorSupport) -> (Gesture, Gesture) {
-    let ruleset = &state.ruleset;
+/* Moves collected for a round - or the result awarded because a player ran out of time */
+enum RoundMoves {
+    Played(Throw, Throw),
+    TimedOut(RoundResult),
+}
 
+fn timeout_penalty(state: &GameState) -> TimeoutPenalty {
+    state
+        .turn_timer
+        .map(|t| t.penalty)
+        .unwrap_or(TimeoutPenalty::RandomGesture)
+}
+
+/* Collects moves for the round */ 
+fn get_round_moves(state: &GameState, color_support: ColorSupport) -> RoundMoves {
     let history_p1 = &state.history_p1;
     let history_p2 = &state.history_p2;
 
     
     /* PLAYER 1 MOVE (HUMAN / AI) */
-    let g1 = match &state.player1.player_type {
-        PlayerType::Human => {
-            let g = show_move_prompt(&state.player1.name, ruleset, color_support);  
-            g.parse::<Gesture>().unwrap()
-        }
-        PlayerType::Computer(diff) => generate_ai_move(diff.clone(), ruleset, history_p2),
+    let t1 = match player_move(state, &state.player1, history_p2, color_support) {
+        Some(t) => t,
+        None => return RoundMoves::TimedOut(RoundResult::Player2Win),
     };
 
     /* CLEAN SCREEN BETWEEN PLAYERS */
@@ -122,13 +198,49 @@ fn get_round_moves(state: &GameState, color_support: ColorSupport) -> (Gesture,
 
    
     /* PLAYER 2 MOVE (HUMAN / AI) */
-    let g2 = match &state.player2.player_type {
-        PlayerType::Human => {
-            let g = show_move_prompt(&state.player2.name, ruleset, color_support);  
-            g.parse::<Gesture>().unwrap()
-        }
-        PlayerType::Computer(diff) => generate_ai_move(diff.clone(), ruleset, history_p1),
+    let t2 = match player_move(state, &state.player2, history_p1, color_support) {
+        Some(t) => t,
+        None => return RoundMoves::TimedOut(RoundResult::Player1Win),
     };
 
-    (g1, g2)
-}
\ No newline at end of file
+    RoundMoves::Played(t1, t2)
+}
+
+/* Single Move (HUMAN / AI) >> None if a human ran out of time and the penalty is a forfeit
+ * Human moves are timed from the moment the prompt appears.
+*/
+fn player_move(
+    state: &GameState,
+    player: &Player,
+    opponent_history: &[Gesture],
+    color_support: ColorSupport,
+) -> Option<Throw> {
+    let ruleset = &state.ruleset;
+
+    match &player.player_type {
+        PlayerType::Human => {
+            let time_limit = state.turn_timer.map(|t| t.seconds);
+            let started = Instant::now();
+
+            match show_move_prompt(&player.name, ruleset, color_support, time_limit) {
+                // Only blitz times count - elsewhere a player may think as long as they like
+                Some(gesture) => Some(Throw {
+                    gesture,
+                    reaction_ms: state.blitz.map(|_| started.elapsed().as_millis() as u64),
+                }),
+                None if timeout_penalty(state) == TimeoutPenalty::RandomGesture => {
+                    show_timeout_notice(&player.name, TimeoutPenalty::RandomGesture, color_support);
+                    Some(Throw {
+                        gesture: generate_ai_move(AiDifficulty::Easy, ruleset, &[]),
+                        reaction_ms: None,
+                    })
+                }
+                None => None,
+            }
+        }
+        PlayerType::Computer(diff) => Some(Throw {
+            gesture: generate_ai_move(diff.clone(), ruleset, opponent_history),
+            reaction_ms: state.blitz.map(|_| ai_reaction_ms(diff)),
+        }),
+    }
+}
@@ -1,6 +1,8 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use super::match_settings::MatchFormat;
+use super::match_settings::{BlitzSettings, MatchFormat, TurnTimer};
+use super::round::RoundRecord;
 use super::rules::{Gesture, Ruleset};
 use crate::players::player::Player;
 
@@ -13,6 +15,14 @@ pub struct GameState {
     pub ruleset: Ruleset,
     pub match_format: MatchFormat,
 
+    /* Per-move countdown (None = players can take as long as they like). */
+    #[serde(default)]
+    pub turn_timer: Option<TurnTimer>,
+
+    /* Blitz mode (None = reaction times don't affect scoring). */
+    #[serde(default)]
+    pub blitz: Option<BlitzSettings>,
+
     pub score_player1: u32,
     pub score_player2: u32,
 
@@ -26,6 +36,22 @@ pub struct GameState {
 
     /* History of all gestures that Player 2 has played (for analytics / AI). */
     pub history_p2: Vec<Gesture>,
+
+    /* Round-by-round record: gestures, results and reaction times. */
+    #[serde(default)]
+    pub rounds: Vec<RoundRecord>,
+
+    /* When the match was last saved (None = never). */
+    #[serde(default)]
+    pub saved_at: Option<DateTime<Utc>>,
+
+    /* Save slot the match was loaded from / saved to (not stored - the slot name is the key). */
+    #[serde(skip)]
+    pub save_slot: Option<String>,
+
+    /* Set on load when the save was changed outside the game (never stored). */
+    #[serde(skip)]
+    pub tampered: bool,
 }
 
 impl GameState {
@@ -34,12 +60,16 @@ impl GameState {
         player2: Player,
         ruleset: Ruleset,
         match_format: MatchFormat,
+        turn_timer: Option<TurnTimer>,
+        blitz: Option<BlitzSettings>,
     ) -> Self {
         GameState {
             player1,
             player2,
             ruleset,
             match_format,
+            turn_timer,
+            blitz,
             score_player1: 0,
             score_player2: 0,
             current_round: 1,
@@ -47,6 +77,10 @@ impl GameState {
             last_move_p2: None,
             history_p1: Vec::new(),
             history_p2: Vec::new(),
+            rounds: Vec::new(),
+            saved_at: None,
+            save_slot: None,
+            tampered: false,
         }
     }
 
@@ -59,5 +93,6 @@ impl GameState {
         self.last_move_p2 = None;
         self.history_p1.clear();
         self.history_p2.clear();
+        self.rounds.clear();
     }
 }
@@ -23,3 +23,30 @@ impl MatchFormat {
         }
     }
 }
+
+/* What happens when a player runs out of time on a move */
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TimeoutPenalty {
+    // A random gesture is thrown for the player
+    RandomGesture,
+
+    // The opponent is awarded the round
+    ForfeitRound,
+
+    // The opponent is awarded the whole match
+    ForfeitMatch,
+}
+
+/* Per-move countdown for human players */
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TurnTimer {
+    pub seconds: u32,
+    pub penalty: TimeoutPenalty,
+}
+
+/* Blitz mode: reaction times count */
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BlitzSettings {
+    // Winning a round with a sub-second throw scores 2 points instead of 1
+    pub sub_second_bonus: bool,
+}
@@ -3,19 +3,50 @@ use super::rules::{beats, Gesture};
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+/* Blitz: throws faster than this earn the bonus point */
+pub const SUB_SECOND_MS: u64 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum RoundResult {
     Player1Win,
     Player2Win,
     Tie,
 }
 
+/* A Gesture + How Long it Took to Enter (None when it wasn't timed) */
+#[derive(Debug, Clone)]
+pub struct Throw {
+    pub gesture: Gesture,
+    pub reaction_ms: Option<u64>,
+}
+
+/* One Finished Round (kept in GameState as the match history) */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoundRecord {
+    // None when the round was forfeited on time
+    pub gesture_p1: Option<Gesture>,
+    pub gesture_p2: Option<Gesture>,
+
+    pub result: RoundResult,
+
+    // Milliseconds each player took to enter the gesture
+    pub reaction_ms_p1: Option<u64>,
+    pub reaction_ms_p2: Option<u64>,
+
+    // Blitz: gestures matched and the faster player took the round
+    #[serde(default)]
+    pub won_on_speed: bool,
+
+    // Blitz: the winner threw in under a second and scored an extra point
+    #[serde(default)]
+    pub bonus_point: bool,
+}
+
 // Process a Single Round >> Updates GameState and Returns The Result
-pub fn resolve_round(
-    state: &mut GameState,
-    gesture_p1: Gesture,
-    gesture_p2: Gesture,
-) -> RoundResult {
+pub fn resolve_round(state: &mut GameState, throw_p1: Throw, throw_p2: Throw) -> RoundResult {
+    let gesture_p1 = throw_p1.gesture;
+    let gesture_p2 = throw_p2.gesture;
+
     state.last_move_p1 = Some(gesture_p1.clone());
     state.last_move_p2 = Some(gesture_p2.clone());
 
@@ -23,7 +54,7 @@ pub fn resolve_round(
     state.history_p1.push(gesture_p1.clone());
     state.history_p2.push(gesture_p2.clone());
 
-    let result = if gesture_p1 == gesture_p2 {
+    let mut result = if gesture_p1 == gesture_p2 {
         RoundResult::Tie
     } else if beats(&gesture_p1, &gesture_p2) {
         RoundResult::Player1Win
@@ -33,12 +64,147 @@ pub fn resolve_round(
         RoundResult::Tie
     };
 
+    /* Blitz: Ties go to The Faster Player, Quick Winners Earn a Bonus */
+    let mut won_on_speed = false;
+    let mut bonus_point = false;
+
+    if let Some(blitz) = state.blitz {
+        if let (RoundResult::Tie, Some(t1), Some(t2)) =
+            (result, throw_p1.reaction_ms, throw_p2.reaction_ms)
+        {
+            if t1 < t2 {
+                result = RoundResult::Player1Win;
+                won_on_speed = true;
+            } else if t2 < t1 {
+                result = RoundResult::Player2Win;
+                won_on_speed = true;
+            }
+        }
+
+        let winner_ms = match result {
+            RoundResult::Player1Win => throw_p1.reaction_ms,
+            RoundResult::Player2Win => throw_p2.reaction_ms,
+            RoundResult::Tie => None,
+        };
+        bonus_point = blitz.sub_second_bonus && winner_ms.is_some_and(|ms| ms < SUB_SECOND_MS);
+    }
+
+    let points = if bonus_point { 2 } else { 1 };
     match result {
+        RoundResult::Player1Win => state.score_player1 += points,
+        RoundResult::Player2Win => state.score_player2 += points,
+        RoundResult::Tie => {}
+    }
+
+    state.rounds.push(RoundRecord {
+        gesture_p1: Some(gesture_p1),
+        gesture_p2: Some(gesture_p2),
+        result,
+        reaction_ms_p1: throw_p1.reaction_ms,
+        reaction_ms_p2: throw_p2.reaction_ms,
+        won_on_speed,
+        bonus_point,
+    });
+
+    result
+}
+
+// Awards a Round Without Gestures (the other player ran out of time)
+pub fn forfeit_round(state: &mut GameState, awarded: RoundResult) -> RoundResult {
+    state.last_move_p1 = None;
+    state.last_move_p2 = None;
+
+    match awarded {
         RoundResult::Player1Win => state.score_player1 += 1,
         RoundResult::Player2Win => state.score_player2 += 1,
         RoundResult::Tie => {}
     }
 
+    state.rounds.push(RoundRecord {
+        gesture_p1: None,
+        gesture_p2: None,
+        result: awarded,
+        reaction_ms_p1: None,
+        reaction_ms_p2: None,
+        won_on_speed: false,
+        bonus_point: false,
+    });
 
-    result
-}
\ No newline at end of file
+    awarded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::match_settings::{BlitzSettings, MatchFormat};
+    use crate::game::rules::Ruleset;
+    use crate::players::player::Player;
+
+    fn state(blitz: Option<BlitzSettings>) -> GameState {
+        GameState::new(
+            Player::new_human("Ana"),
+            Player::new_human("Ben"),
+            Ruleset::Classic,
+            MatchFormat::FirstTo(3),
+            None,
+            blitz,
+        )
+    }
+
+    fn throw(gesture: Gesture, reaction_ms: u64) -> Throw {
+        Throw {
+            gesture,
+            reaction_ms: Some(reaction_ms),
+        }
+    }
+
+    #[test]
+    fn ties_stay_ties_outside_blitz() {
+        let mut state = state(None);
+        let result = resolve_round(&mut state, throw(Gesture::Rock, 300), throw(Gesture::Rock, 900));
+
+        assert_eq!(result, RoundResult::Tie);
+        assert_eq!((state.score_player1, state.score_player2), (0, 0));
+        assert!(!state.rounds[0].won_on_speed);
+    }
+
+    #[test]
+    fn blitz_ties_go_to_the_faster_throw() {
+        let mut state = state(Some(BlitzSettings { sub_second_bonus: false }));
+        let result = resolve_round(&mut state, throw(Gesture::Paper, 1400), throw(Gesture::Paper, 1200));
+
+        assert_eq!(result, RoundResult::Player2Win);
+        assert_eq!(state.score_player2, 1);
+        assert!(state.rounds[0].won_on_speed && !state.rounds[0].bonus_point);
+
+        // Same time: still a tie
+        let result = resolve_round(&mut state, throw(Gesture::Rock, 800), throw(Gesture::Rock, 800));
+        assert_eq!(result, RoundResult::Tie);
+    }
+
+    #[test]
+    fn sub_second_bonus_needs_the_setting_and_a_fast_winner() {
+        let mut without = state(Some(BlitzSettings { sub_second_bonus: false }));
+        resolve_round(&mut without, throw(Gesture::Rock, 400), throw(Gesture::Scissors, 2000));
+        assert_eq!(without.score_player1, 1);
+
+        let mut with = state(Some(BlitzSettings { sub_second_bonus: true }));
+        resolve_round(&mut with, throw(Gesture::Rock, 400), throw(Gesture::Scissors, 2000));
+        assert_eq!(with.score_player1, 2);
+        assert!(with.rounds[0].bonus_point);
+
+        // A fast loser doesn't help the slow winner
+        resolve_round(&mut with, throw(Gesture::Rock, 1500), throw(Gesture::Paper, SUB_SECOND_MS));
+        assert_eq!(with.score_player2, 1);
+        assert!(!with.rounds[1].bonus_point);
+    }
+
+    #[test]
+    fn forfeited_rounds_score_one_point_without_gestures() {
+        let mut state = state(Some(BlitzSettings { sub_second_bonus: true }));
+        forfeit_round(&mut state, RoundResult::Player1Win);
+
+        assert_eq!(state.score_player1, 1);
+        assert!(state.rounds[0].gesture_p1.is_none() && !state.rounds[0].bonus_point);
+    }
+}
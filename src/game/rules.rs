@@ -29,7 +29,7 @@ impl FromStr for Gesture {
 
 
 // Ruleset: Classic (RPS) or Extended (RPSLS)
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum Ruleset {
     Classic,
     Extended,
@@ -0,0 +1,48 @@
+use std::collections::HashMap;
+
+use super::match_record::MatchRecord;
+use crate::game::rules::Gesture;
+use crate::scoreboard::scoreboard::Scoreboard;
+
+/* Most Common Gesture Pairings Between Two Players (by profile ID)
+ * Pairs are (what `player` threw, what `opponent` threw), most frequent first.
+*/
+pub fn gesture_pairings(
+    records: &[MatchRecord],
+    scoreboard: &Scoreboard,
+    player: &str,
+    opponent: &str,
+) -> Vec<((Gesture, Gesture), u32)> {
+    let mut counts: HashMap<(Gesture, Gesture), u32> = HashMap::new();
+
+    for record in records {
+        let ids = (scoreboard.profile_id(&record.player1), scoreboard.profile_id(&record.player2));
+        let swapped = if ids == (Some(player), Some(opponent)) {
+            false
+        } else if ids == (Some(opponent), Some(player)) {
+            true
+        } else {
+            continue;
+        };
+
+        for round in &record.rounds {
+            if let (Some(g1), Some(g2)) = (&round.gesture_p1, &round.gesture_p2) {
+                let pair = if swapped {
+                    (g2.clone(), g1.clone())
+                } else {
+                    (g1.clone(), g2.clone())
+                };
+                *counts.entry(pair).or_insert(0) += 1;
+            }
+        }
+    }
+
+    let mut pairings: Vec<((Gesture, Gesture), u32)> = counts.into_iter().collect();
+    pairings.sort_by(|(a, count_a), (b, count_b)| {
+        count_b
+            .cmp(count_a)
+            .then_with(|| a.0.as_str().cmp(b.0.as_str()))
+            .then_with(|| a.1.as_str().cmp(b.1.as_str()))
+    });
+    pairings
+}
@@ -0,0 +1,163 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::game::game_state::GameState;
+use crate::game::match_settings::{BlitzSettings, MatchFormat, TurnTimer};
+use crate::game::round::{RoundRecord, RoundResult};
+use crate::game::rules::Ruleset;
+use crate::players::player::Player;
+use crate::scoreboard::stats::MatchOutcome;
+use crate::utils::id::new_id;
+
+/* One Finished Match - everything needed to replay it or recompute stats from it */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchRecord {
+    pub id: String,
+    pub played_at: DateTime<Utc>,
+
+    pub player1: Player,
+    pub player2: Player,
+
+    pub ruleset: Ruleset,
+    pub match_format: MatchFormat,
+    pub turn_timer: Option<TurnTimer>,
+    pub blitz: Option<BlitzSettings>,
+
+    pub rounds: Vec<RoundRecord>,
+
+    pub score_player1: u32,
+    pub score_player2: u32,
+    pub outcome: MatchOutcome,
+}
+
+impl MatchRecord {
+    pub fn from_state(state: &GameState, outcome: MatchOutcome) -> Self {
+        MatchRecord {
+            id: new_id(),
+            played_at: Utc::now(),
+            player1: state.player1.clone(),
+            player2: state.player2.clone(),
+            ruleset: state.ruleset.clone(),
+            match_format: state.match_format.clone(),
+            turn_timer: state.turn_timer,
+            blitz: state.blitz,
+            rounds: state.rounds.clone(),
+            score_player1: state.score_player1,
+            score_player2: state.score_player2,
+            outcome,
+        }
+    }
+
+    pub fn winner_name(&self) -> Option<String> {
+        match self.outcome {
+            MatchOutcome::Player1Win => Some(self.player1.name.clone()),
+            MatchOutcome::Player2Win => Some(self.player2.name.clone()),
+            MatchOutcome::Tie => None,
+        }
+    }
+
+    /* Rebuilds the GameState as it was after the first `rounds_played` rounds (for replays) */
+    pub fn state_after(&self, rounds_played: usize) -> GameState {
+        let mut state = GameState::new(
+            self.player1.clone(),
+            self.player2.clone(),
+            self.ruleset.clone(),
+            self.match_format.clone(),
+            self.turn_timer,
+            self.blitz,
+        );
+
+        for round in self.rounds.iter().take(rounds_played) {
+            let points = if round.bonus_point { 2 } else { 1 };
+            match round.result {
+                RoundResult::Player1Win => state.score_player1 += points,
+                RoundResult::Player2Win => state.score_player2 += points,
+                RoundResult::Tie => {}
+            }
+
+            if let (Some(g1), Some(g2)) = (&round.gesture_p1, &round.gesture_p2) {
+                state.history_p1.push(g1.clone());
+                state.history_p2.push(g2.clone());
+            }
+            state.last_move_p1 = round.gesture_p1.clone();
+            state.last_move_p2 = round.gesture_p2.clone();
+            state.rounds.push(round.clone());
+            state.current_round += 1;
+        }
+
+        /* The final screen always shows the recorded score (covers a match forfeited on time) */
+        if rounds_played >= self.rounds.len() {
+            state.score_player1 = self.score_player1;
+            state.score_player2 = self.score_player2;
+        }
+
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::match_settings::{TimeoutPenalty, TurnTimer};
+    use crate::game::round::{forfeit_round, resolve_round, Throw};
+    use crate::game::rules::Gesture;
+
+    fn throw(gesture: Gesture, reaction_ms: u64) -> Throw {
+        Throw {
+            gesture,
+            reaction_ms: Some(reaction_ms),
+        }
+    }
+
+    // Bonus win for Ana, a tie, then Ben runs out of time and forfeits the match
+    fn recorded_match() -> MatchRecord {
+        let mut state = GameState::new(
+            Player::new_human("Ana"),
+            Player::new_human("Ben"),
+            Ruleset::Classic,
+            MatchFormat::FirstTo(5),
+            Some(TurnTimer { seconds: 5, penalty: TimeoutPenalty::ForfeitMatch }),
+            Some(BlitzSettings { sub_second_bonus: true }),
+        );
+        resolve_round(&mut state, throw(Gesture::Paper, 500), throw(Gesture::Rock, 1500));
+        resolve_round(&mut state, throw(Gesture::Rock, 900), throw(Gesture::Rock, 900));
+        forfeit_round(&mut state, RoundResult::Player1Win);
+        state.score_player1 = 5;
+        MatchRecord::from_state(&state, MatchOutcome::Player1Win)
+    }
+
+    #[test]
+    fn replay_starts_from_an_empty_board() {
+        let state = recorded_match().state_after(0);
+
+        assert_eq!((state.score_player1, state.score_player2), (0, 0));
+        assert_eq!(state.current_round, 1);
+        assert!(state.rounds.is_empty() && state.last_move_p1.is_none());
+    }
+
+    #[test]
+    fn replay_steps_rebuild_scores_and_gestures() {
+        let record = recorded_match();
+
+        let state = record.state_after(1);
+        assert_eq!((state.score_player1, state.score_player2), (2, 0));
+        assert_eq!(state.last_move_p1, Some(Gesture::Paper));
+
+        let state = record.state_after(2);
+        assert_eq!(state.score_player1, 2);
+        assert_eq!(state.history_p2, [Gesture::Rock, Gesture::Rock]);
+        assert_eq!(state.current_round, 3);
+    }
+
+    #[test]
+    fn last_step_shows_the_recorded_score() {
+        let record = recorded_match();
+        let state = record.state_after(record.rounds.len());
+
+        // The forfeited round leaves no gestures behind
+        assert!(state.last_move_p1.is_none());
+        assert_eq!(state.history_p1.len(), 2);
+        assert_eq!((state.score_player1, state.score_player2), (5, 0));
+        assert_eq!(record.winner_name().as_deref(), Some("Ana"));
+    }
+}
@@ -0,0 +1,3 @@
+pub mod match_record;
+pub mod analytics;
+pub mod query;
@@ -0,0 +1,40 @@
+use chrono::{DateTime, Utc};
+
+use super::match_record::MatchRecord;
+use crate::game::rules::Ruleset;
+use crate::players::profile::normalize_name;
+
+/* Which Recorded Matches to Fetch (every field is optional, empty = all matches)
+ * e.g. all of one player's Extended matches in October.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct MatchQuery {
+    // Profile ID of a player who took part (either side)
+    pub player_id: Option<String>,
+    // The names that player went by (display name + aliases): matches logged before profiles existed
+    // carry no ID, and a merged profile's ID is gone - those are matched by name instead
+    pub player_names: Vec<String>,
+    pub ruleset: Option<Ruleset>,
+    // Played at or after `from`, and before `until`
+    pub from: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl MatchQuery {
+    pub fn matches(&self, record: &MatchRecord) -> bool {
+        let took_part = |id: &String| {
+            [&record.player1, &record.player2].iter().any(|p| {
+                p.profile_id.as_ref() == Some(id)
+                    || self
+                        .player_names
+                        .iter()
+                        .any(|name| normalize_name(name) == normalize_name(&p.name))
+            })
+        };
+
+        self.player_id.as_ref().is_none_or(took_part)
+            && self.ruleset.as_ref().is_none_or(|r| *r == record.ruleset)
+            && self.from.is_none_or(|from| record.played_at >= from)
+            && self.until.is_none_or(|until| record.played_at < until)
+    }
+}
@@ -0,0 +1,412 @@
+use std::io::{ErrorKind, IsTerminal};
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::display::colors::ColorSupport;
+use crate::game::match_settings::{MatchFormat, TimeoutPenalty, TurnTimer};
+use crate::game::rules::Ruleset;
+use crate::io::file_manager::read_from_file;
+use crate::players::ai_difficulty::AiDifficulty;
+use crate::players::player::DEFAULT_AI_NAME;
+use crate::players::profile::{clean_name, ColorTheme};
+
+/* Configuration (one TOML file, every key optional)
+ * $PL_CONFIG, else $XDG_CONFIG_HOME/pl-project/config.toml, else ~/.config/pl-project/config.toml
+ * (%APPDATA%\pl-project\config.toml on Windows). PL_* environment variables override single keys.
+ *
+ *   data_dir = "~/games/pl-project"   # PL_DATA_DIR (absolute, or starting with ~/)
+ *   color = "auto"                    # PL_COLOR       auto | always | never
+ *   theme = "neon"                    # PL_THEME       neon | plain
+ *   ai_name = "Computer"              # PL_AI_NAME
+ *
+ *   [defaults]                        # offered on ENTER during match setup
+ *   ruleset = "extended"              # PL_RULESET     classic | extended
+ *   format = "best-of-3"              # PL_FORMAT      single | best-of-N (odd) | first-to-K
+ *   difficulty = "normal"             # PL_DIFFICULTY  easy | normal | hard
+ *
+ *   [timer]
+ *   seconds = 10                      # PL_TIMER_SECONDS  0 = no timer
+ *   on_timeout = "random"             # PL_TIMER_PENALTY  random | forfeit-round | forfeit-match
+ *
+ *   [ratings]                         # written into the scoreboard on start (replays use them too)
+ *   elo_k_factor = 32                 # PL_ELO_K_FACTOR        1 - 100
+ *   period_days = 7                   # PL_RATING_PERIOD_DAYS  1 - 365, Glicko-2 rating period
+*/
+const APP_DIR: &str = "pl-project";
+const CONFIG_FILE: &str = "config.toml";
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    // Where saves live, from PL_DATA_DIR or the file (None = the usual place, see storage::data_dir)
+    pub data_dir: Option<PathBuf>,
+    pub color: ColorMode,
+    // Colours outside a match, and for players without a profile theme
+    pub theme: ColorTheme,
+    // Name every AI opponent plays under
+    pub ai_name: String,
+    pub defaults: GameDefaults,
+    pub ratings: RatingSettings,
+}
+
+/* Suggested match settings (a player's own profile preferences come first) */
+#[derive(Debug, Clone)]
+pub struct GameDefaults {
+    pub ruleset: Option<Ruleset>,
+    pub format: Option<MatchFormat>,
+    pub difficulty: Option<AiDifficulty>,
+    pub turn_timer: Option<TurnTimer>,
+}
+
+/* Scoreboard-wide rating settings (None = keep what the scoreboard has) */
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RatingSettings {
+    pub elo_k_factor: Option<f64>,
+    pub period_days: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorMode {
+    // Colours when printing to a terminal (and NO_COLOR isn't set)
+    Auto,
+    Always,
+    Never,
+}
+
+impl Config {
+    pub fn color_support(&self) -> ColorSupport {
+        match self.color {
+            ColorMode::Never => ColorSupport::Disabled,
+            _ => self.theme.color_support(),
+        }
+    }
+
+    /* Applies the colour mode to all output, including screens that don't take a ColorSupport */
+    pub fn apply_color_mode(&self) {
+        match self.color {
+            ColorMode::Always => colored::control::set_override(true),
+            ColorMode::Never => colored::control::set_override(false),
+            ColorMode::Auto if !std::io::stdout().is_terminal() => {
+                colored::control::set_override(false)
+            }
+            ColorMode::Auto => {}
+        }
+    }
+}
+
+pub fn config_path() -> PathBuf {
+    let env_dir = |name: &str| {
+        std::env::var_os(name)
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+    };
+
+    if let Some(file) = env_dir("PL_CONFIG") {
+        return file;
+    }
+    if let Some(xdg) = env_dir("XDG_CONFIG_HOME").filter(|d| d.is_absolute()) {
+        return xdg.join(APP_DIR).join(CONFIG_FILE);
+    }
+    if cfg!(windows)
+        && let Some(appdata) = env_dir("APPDATA")
+    {
+        return appdata.join(APP_DIR).join(CONFIG_FILE);
+    }
+    match env_dir("HOME") {
+        Some(home) => home.join(".config").join(APP_DIR).join(CONFIG_FILE),
+        None => PathBuf::from(CONFIG_FILE),
+    }
+}
+
+/* The file as written - values stay strings until they're validated */
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawConfig {
+    data_dir: Option<String>,
+    color: Option<String>,
+    theme: Option<String>,
+    ai_name: Option<String>,
+    #[serde(default)]
+    defaults: RawDefaults,
+    #[serde(default)]
+    timer: RawTimer,
+    #[serde(default)]
+    ratings: RawRatings,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawDefaults {
+    ruleset: Option<String>,
+    format: Option<String>,
+    difficulty: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawTimer {
+    seconds: Option<u32>,
+    on_timeout: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawRatings {
+    elo_k_factor: Option<f64>,
+    period_days: Option<u32>,
+}
+
+/* Reads the config file (a missing one means all defaults) and the PL_* overrides
+ * Err lists every bad setting, with where it came from.
+*/
+pub fn load_config() -> Result<Config, String> {
+    let path = config_path();
+    let raw = match read_from_file(&path) {
+        Ok(text) => toml::from_str::<RawConfig>(&text)
+            .map_err(|e| format!("Invalid config file {}:\n{e}", path.display()))?,
+        Err(e) if e.kind() == ErrorKind::NotFound => RawConfig::default(),
+        Err(e) => return Err(format!("Read config {} failed: {e}", path.display())),
+    };
+
+    let mut settings = Settings {
+        path: &path,
+        errors: Vec::new(),
+    };
+    let data_dir = settings.check("data_dir", raw.data_dir, Some("PL_DATA_DIR"), parse_dir);
+    let color = settings.check("color", raw.color, Some("PL_COLOR"), parse_color_mode);
+    let theme = settings.check("theme", raw.theme, Some("PL_THEME"), parse_theme);
+    let ai_name = settings.check("ai_name", raw.ai_name, Some("PL_AI_NAME"), parse_ai_name);
+
+    let defaults = raw.defaults;
+    let ruleset =
+        settings.check("defaults.ruleset", defaults.ruleset, Some("PL_RULESET"), parse_ruleset);
+    let format =
+        settings.check("defaults.format", defaults.format, Some("PL_FORMAT"), parse_format);
+    let difficulty = settings.check(
+        "defaults.difficulty",
+        defaults.difficulty,
+        Some("PL_DIFFICULTY"),
+        parse_difficulty,
+    );
+
+    let seconds = settings.check(
+        "timer.seconds",
+        raw.timer.seconds.map(|s| s.to_string()),
+        Some("PL_TIMER_SECONDS"),
+        parse_seconds,
+    );
+    let penalty = settings.check(
+        "timer.on_timeout",
+        raw.timer.on_timeout,
+        Some("PL_TIMER_PENALTY"),
+        parse_penalty,
+    );
+    // A penalty alone would silently do nothing (seconds = 0 is the timer switched off on purpose)
+    if penalty.is_some() && seconds.is_none() {
+        settings.errors.push(format!(
+            "timer.on_timeout (or PL_TIMER_PENALTY) is set without timer.seconds in {} (or PL_TIMER_SECONDS)",
+            path.display()
+        ));
+    }
+    let turn_timer = match seconds {
+        Some(seconds) if seconds > 0 => Some(TurnTimer {
+            seconds,
+            penalty: penalty.unwrap_or(TimeoutPenalty::RandomGesture),
+        }),
+        _ => None,
+    };
+
+    let elo_k_factor = settings.check(
+        "ratings.elo_k_factor",
+        raw.ratings.elo_k_factor.map(|k| k.to_string()),
+        Some("PL_ELO_K_FACTOR"),
+        parse_k_factor,
+    );
+    let period_days = settings.check(
+        "ratings.period_days",
+        raw.ratings.period_days.map(|d| d.to_string()),
+        Some("PL_RATING_PERIOD_DAYS"),
+        parse_period_days,
+    );
+
+    // NO_COLOR (no-color.org) counts unless colours were asked for explicitly
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    let config = Config {
+        data_dir,
+        color: color.unwrap_or(if no_color { ColorMode::Never } else { ColorMode::Auto }),
+        theme: theme.unwrap_or(ColorTheme::Neon),
+        ai_name: ai_name.unwrap_or_else(|| DEFAULT_AI_NAME.to_string()),
+        defaults: GameDefaults {
+            ruleset,
+            format,
+            difficulty,
+            turn_timer,
+        },
+        ratings: RatingSettings {
+            elo_k_factor,
+            period_days,
+        },
+    };
+
+    if settings.errors.is_empty() {
+        Ok(config)
+    } else {
+        Err(format!(
+            "Invalid configuration:\n  - {}",
+            settings.errors.join("\n  - ")
+        ))
+    }
+}
+
+/* Collects the problems instead of stopping at the first one */
+struct Settings<'a> {
+    path: &'a Path,
+    errors: Vec<String>,
+}
+
+impl Settings<'_> {
+    // The environment variable (if the key has one) wins over the file
+    fn check<T>(
+        &mut self,
+        key: &str,
+        from_file: Option<String>,
+        env: Option<&str>,
+        parse: impl Fn(&str) -> Result<T, String>,
+    ) -> Option<T> {
+        let from_env = env.and_then(|name| {
+            let value = std::env::var(name).ok()?;
+            (!value.trim().is_empty()).then(|| (value, name.to_string()))
+        });
+        let (value, origin) = match from_env {
+            Some(found) => found,
+            None => (from_file?, format!("{key} in {}", self.path.display())),
+        };
+
+        match parse(value.trim()) {
+            Ok(parsed) => Some(parsed),
+            Err(e) => {
+                self.errors.push(format!("{origin} = \"{value}\": {e}"));
+                None
+            }
+        }
+    }
+}
+
+fn parse_dir(text: &str) -> Result<PathBuf, String> {
+    let path = match text.strip_prefix("~/") {
+        Some(rest) => std::env::var_os("HOME")
+            .map(|home| PathBuf::from(home).join(rest))
+            .ok_or("'~' needs HOME to be set")?,
+        None => PathBuf::from(text),
+    };
+    if !path.is_absolute() {
+        return Err("use an absolute path (or one starting with ~/)".to_string());
+    }
+    Ok(path)
+}
+
+fn parse_color_mode(text: &str) -> Result<ColorMode, String> {
+    match text.to_lowercase().as_str() {
+        "auto" => Ok(ColorMode::Auto),
+        "always" => Ok(ColorMode::Always),
+        "never" => Ok(ColorMode::Never),
+        _ => Err("expected auto, always or never".to_string()),
+    }
+}
+
+fn parse_theme(text: &str) -> Result<ColorTheme, String> {
+    match text.to_lowercase().as_str() {
+        "neon" => Ok(ColorTheme::Neon),
+        "plain" => Ok(ColorTheme::Plain),
+        _ => Err("expected neon or plain".to_string()),
+    }
+}
+
+fn parse_ai_name(text: &str) -> Result<String, String> {
+    let name = clean_name(text);
+    if name.is_empty() {
+        return Err("the AI needs a name".to_string());
+    }
+    if name.chars().count() > 20 {
+        return Err("keep it to 20 characters".to_string());
+    }
+    Ok(name)
+}
+
+fn parse_ruleset(text: &str) -> Result<Ruleset, String> {
+    match text.to_lowercase().as_str() {
+        "classic" => Ok(Ruleset::Classic),
+        "extended" => Ok(Ruleset::Extended),
+        _ => Err("expected classic or extended".to_string()),
+    }
+}
+
+/* "single", "best-of-5", "first-to-3" */
+fn parse_format(text: &str) -> Result<MatchFormat, String> {
+    const EXPECTED: &str = "expected single, best-of-N (N odd, e.g. best-of-3) or first-to-K";
+    let text = text.to_lowercase();
+    if text == "single" {
+        return Ok(MatchFormat::SingleRound);
+    }
+
+    let number = |rest: &str| rest.parse::<u32>().map_err(|_| EXPECTED.to_string());
+    if let Some(rest) = text.strip_prefix("best-of-") {
+        let n = number(rest)?;
+        if n == 0 || n % 2 == 0 {
+            return Err(format!("best-of needs an odd number of rounds, not {n}"));
+        }
+        return Ok(MatchFormat::BestOf(n));
+    }
+    if let Some(rest) = text.strip_prefix("first-to-") {
+        let k = number(rest)?;
+        if k == 0 {
+            return Err("first-to needs at least 1 win".to_string());
+        }
+        return Ok(MatchFormat::FirstTo(k));
+    }
+    Err(EXPECTED.to_string())
+}
+
+fn parse_difficulty(text: &str) -> Result<AiDifficulty, String> {
+    match text.to_lowercase().as_str() {
+        "easy" => Ok(AiDifficulty::Easy),
+        "normal" => Ok(AiDifficulty::Normal),
+        "hard" => Ok(AiDifficulty::Hard),
+        _ => Err("expected easy, normal or hard".to_string()),
+    }
+}
+
+fn parse_seconds(text: &str) -> Result<u32, String> {
+    let seconds: u32 = text
+        .parse()
+        .map_err(|_| "expected a whole number of seconds (0 = no timer)".to_string())?;
+    if seconds > 600 {
+        return Err("at most 600 seconds per move".to_string());
+    }
+    Ok(seconds)
+}
+
+fn parse_penalty(text: &str) -> Result<TimeoutPenalty, String> {
+    match text.to_lowercase().as_str() {
+        "random" => Ok(TimeoutPenalty::RandomGesture),
+        "forfeit-round" => Ok(TimeoutPenalty::ForfeitRound),
+        "forfeit-match" => Ok(TimeoutPenalty::ForfeitMatch),
+        _ => Err("expected random, forfeit-round or forfeit-match".to_string()),
+    }
+}
+
+fn parse_k_factor(text: &str) -> Result<f64, String> {
+    let k: f64 = text.parse().map_err(|_| "expected a number (32 is usual)".to_string())?;
+    if !(1.0..=100.0).contains(&k) {
+        return Err("keep the K-factor between 1 and 100".to_string());
+    }
+    Ok(k)
+}
+
+fn parse_period_days(text: &str) -> Result<u32, String> {
+    let days: u32 = text.parse().map_err(|_| "expected a whole number of days".to_string())?;
+    if !(1..=365).contains(&days) {
+        return Err("a rating period lasts 1 to 365 days".to_string());
+    }
+    Ok(days)
+}
@@ -0,0 +1,350 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::io::integrity::{Integrity, Seal, SecretKey, check, seal};
+use crate::scoreboard::scoreboard::migrate_scoreboard_v1_to_v2;
+
+/* Versioned Envelope around everything that gets persisted
+ * { "format": "scoreboard", "version": 2, "data": { ... }, "seal": { ... } }
+ * Bare JSON (anything saved before the envelope existed) counts as version 1 - or 2 for a scoreboard
+ * that already has player profiles (see FileKind::bare_version).
+*/
+#[derive(Serialize, Deserialize)]
+struct Envelope {
+    format: String,
+    version: u32,
+    data: Value,
+
+    /* Tamper-detection signatures (see integrity.rs) */
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    seal: Option<Seal>,
+}
+
+/* Upgrades `data` by exactly one version */
+type Migration = fn(Value) -> Result<Value, String>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileKind {
+    GameState,
+    Scoreboard,
+    Achievements,
+    MatchRecord,
+}
+
+impl FileKind {
+    fn format(self) -> &'static str {
+        match self {
+            FileKind::GameState => "game_state",
+            FileKind::Scoreboard => "scoreboard",
+            FileKind::Achievements => "achievements",
+            FileKind::MatchRecord => "match",
+        }
+    }
+
+    /* Used in error messages ("Parse state failed: ...") */
+    pub fn label(self) -> &'static str {
+        match self {
+            FileKind::GameState => "state",
+            FileKind::Scoreboard => "scoreboard",
+            FileKind::Achievements => "achievements",
+            FileKind::MatchRecord => "match",
+        }
+    }
+
+    /* migrations()[0] upgrades version 1 to 2, [1] upgrades 2 to 3, ...
+     * Changing a persisted struct in a way serde defaults can't cover? Add a step here.
+     */
+    fn migrations(self) -> &'static [Migration] {
+        match self {
+            FileKind::GameState => &[envelope_only],
+            FileKind::Scoreboard => &[migrate_scoreboard_v1_to_v2],
+            FileKind::Achievements => &[envelope_only],
+            FileKind::MatchRecord => &[envelope_only],
+        }
+    }
+
+    pub fn current_version(self) -> u32 {
+        self.migrations().len() as u32 + 1
+    }
+
+    /* Which version a file from before the envelope is
+     * Scoreboards got player profiles (v2) while they were still bare JSON, so look at the data.
+     */
+    fn bare_version(self, data: &Value) -> u32 {
+        match self {
+            FileKind::Scoreboard if data.get("profiles").is_some() => 2,
+            _ => 1,
+        }
+    }
+
+    /* Parts signed on their own, so a tampered file can say *which* entries changed */
+    fn entries(self, data: &Value) -> Vec<(String, &Value)> {
+        match self {
+            FileKind::Scoreboard | FileKind::Achievements => data
+                .get("players")
+                .and_then(Value::as_object)
+                .map(|players| players.iter().map(|(id, v)| (id.clone(), v)).collect())
+                .unwrap_or_default(),
+            FileKind::GameState | FileKind::MatchRecord => Vec::new(),
+        }
+    }
+}
+
+// Version 2 only added the envelope - the data itself is unchanged (everything but the scoreboard)
+fn envelope_only(data: Value) -> Result<Value, String> {
+    Ok(data)
+}
+
+/* Serializes `value` inside an envelope stamped with the current version */
+pub fn to_json<T: Serialize>(kind: FileKind, value: &T, pretty: bool) -> Result<String, String> {
+    write(kind, value, pretty, None)
+}
+
+/* Same, signed with `key` */
+pub fn to_signed_json<T: Serialize>(
+    kind: FileKind,
+    value: &T,
+    pretty: bool,
+    key: &SecretKey,
+) -> Result<String, String> {
+    write(kind, value, pretty, Some(key))
+}
+
+fn write<T: Serialize>(
+    kind: FileKind,
+    value: &T,
+    pretty: bool,
+    key: Option<&SecretKey>,
+) -> Result<String, String> {
+    let version = kind.current_version();
+    let data = serde_json::to_value(value).map_err(|e| e.to_string())?;
+    let seal = key.map(|key| seal(key, kind.format(), version, &data, &kind.entries(&data)));
+    let envelope = Envelope {
+        format: kind.format().to_string(),
+        version,
+        data,
+        seal,
+    };
+
+    if pretty {
+        serde_json::to_string_pretty(&envelope)
+    } else {
+        serde_json::to_string(&envelope)
+    }
+    .map_err(|e| e.to_string())
+}
+
+/* Parses any version of a `kind` file, migrating older ones up to the current version */
+pub fn from_json<T: DeserializeOwned>(kind: FileKind, text: &str) -> Result<T, String> {
+    read(kind, text, None).map(|(value, _)| value)
+}
+
+/* Same, also checking the signatures against `key` */
+pub fn from_signed_json<T: DeserializeOwned>(
+    kind: FileKind,
+    text: &str,
+    key: &SecretKey,
+) -> Result<(T, Integrity), String> {
+    read(kind, text, Some(key))
+}
+
+/* Whether `text` carries signatures at all (made with any key - they aren't checked here) */
+pub fn is_sealed(kind: FileKind, text: &str) -> bool {
+    serde_json::from_str::<Value>(text)
+        .ok()
+        .and_then(|value| open_envelope(kind, value).ok())
+        .is_some_and(|envelope| envelope.seal.is_some())
+}
+
+// Without a key there is nothing to check, and the result says Unsigned
+fn read<T: DeserializeOwned>(
+    kind: FileKind,
+    text: &str,
+    key: Option<&SecretKey>,
+) -> Result<(T, Integrity), String> {
+    let value: Value = serde_json::from_str(text).map_err(|e| e.to_string())?;
+    let envelope = open_envelope(kind, value)?;
+
+    // Signatures cover the data as it was written, so check before migrating
+    let integrity = match key {
+        Some(key) => check(
+            key,
+            kind.format(),
+            envelope.version,
+            &envelope.data,
+            &kind.entries(&envelope.data),
+            envelope.seal.as_ref(),
+        ),
+        None => Integrity::Unsigned,
+    };
+
+    let data = migrate(kind, envelope.version, envelope.data)?;
+    let value = serde_json::from_value(data).map_err(|e| e.to_string())?;
+    Ok((value, integrity))
+}
+
+// Bare JSON becomes an envelope around everything, versioned by what it looks like
+fn open_envelope(kind: FileKind, value: Value) -> Result<Envelope, String> {
+    const FIELDS: [&str; 4] = ["format", "version", "data", "seal"];
+    let is_envelope = value.as_object().is_some_and(|o| {
+        FIELDS[..3].iter().all(|k| o.contains_key(*k))
+            && o.keys().all(|k| FIELDS.contains(&k.as_str()))
+    });
+    if !is_envelope {
+        return Ok(Envelope {
+            format: kind.format().to_string(),
+            version: kind.bare_version(&value),
+            data: value,
+            seal: None,
+        });
+    }
+
+    let envelope: Envelope = serde_json::from_value(value).map_err(|e| e.to_string())?;
+    if envelope.format != kind.format() {
+        return Err(format!(
+            "expected a {} file, found '{}'",
+            kind.format(),
+            envelope.format
+        ));
+    }
+    Ok(envelope)
+}
+
+fn migrate(kind: FileKind, version: u32, mut data: Value) -> Result<Value, String> {
+    let current = kind.current_version();
+    if version == 0 || version > current {
+        return Err(format!(
+            "{} version {version} is not supported (this build reads up to version {current})",
+            kind.format()
+        ));
+    }
+
+    for (from, step) in kind
+        .migrations()
+        .iter()
+        .enumerate()
+        .skip(version as usize - 1)
+    {
+        data = step(data).map_err(|e| {
+            format!(
+                "migrating {} from version {} failed: {e}",
+                kind.format(),
+                from + 1
+            )
+        })?;
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::achievements::tracker::AchievementBook;
+    use crate::game::game_state::GameState;
+    use crate::game::match_settings::MatchFormat;
+    use crate::game::rules::{Gesture, Ruleset};
+    use crate::history::match_record::MatchRecord;
+    use crate::players::player::PlayerType;
+    use crate::scoreboard::scoreboard::Scoreboard;
+
+    /* Files exactly as each version wrote them */
+    const V1_SAVE_STATE: &str = include_str!("../../tests/fixtures/v1/save_state.json");
+    const V1_SCOREBOARD: &str = include_str!("../../tests/fixtures/v1/scoreboard.json");
+    const V1_ACHIEVEMENTS: &str = include_str!("../../tests/fixtures/v1/achievements.json");
+    const V1_MATCH_LOG: &str = include_str!("../../tests/fixtures/v1/match_log.jsonl");
+    const V2_SAVE_STATE: &str = include_str!("../../tests/fixtures/v2/save_state.json");
+    const V2_SCOREBOARD: &str = include_str!("../../tests/fixtures/v2/scoreboard.json");
+    // Profiles already, but written before the envelope
+    const V2_SCOREBOARD_BARE: &str = include_str!("../../tests/fixtures/v2/scoreboard_bare.json");
+
+    #[test]
+    fn v1_save_state_loads() {
+        let state: GameState = from_json(FileKind::GameState, V1_SAVE_STATE).unwrap();
+
+        assert_eq!(state.player1.name, "Harkez");
+        assert!(matches!(state.player2.player_type, PlayerType::Computer(_)));
+        assert_eq!(state.ruleset, Ruleset::Extended);
+        assert!(matches!(state.match_format, MatchFormat::SingleRound));
+        assert_eq!(state.history_p1, vec![Gesture::Rock]);
+        assert!(state.rounds.is_empty());
+        assert!(state.turn_timer.is_none() && state.saved_at.is_none());
+    }
+
+    #[test]
+    fn v1_name_keyed_scoreboard_migrates_to_profiles() {
+        let value: Value = serde_json::from_str(V1_SCOREBOARD).unwrap();
+        let envelope = open_envelope(FileKind::Scoreboard, value).unwrap();
+        assert_eq!(envelope.version, 1);
+        assert!(envelope.data["players"].get("Haris").is_some());
+
+        let data = migrate(FileKind::Scoreboard, envelope.version, envelope.data).unwrap();
+        let sb: Scoreboard = serde_json::from_value(data).unwrap();
+        let haris = sb.find_profile("HARIS").unwrap().id.clone();
+        assert_eq!(sb.profiles.len(), 3);
+        assert_eq!(sb.players[&haris].matches_played, 5);
+        assert!(sb.players.keys().all(|id| sb.profiles.contains_key(id)));
+
+        // from_json runs the same chain
+        let sb: Scoreboard = from_json(FileKind::Scoreboard, V1_SCOREBOARD).unwrap();
+        assert!(!sb.players.contains_key("Haris") && sb.find_profile("tarik").is_some());
+    }
+
+    #[test]
+    fn bare_scoreboard_with_profiles_is_v2() {
+        let value: Value = serde_json::from_str(V2_SCOREBOARD_BARE).unwrap();
+        assert_eq!(open_envelope(FileKind::Scoreboard, value).unwrap().version, 2);
+
+        let sb: Scoreboard = from_json(FileKind::Scoreboard, V2_SCOREBOARD_BARE).unwrap();
+        let asmir = sb.find_profile("asmir").unwrap();
+        assert_eq!(sb.players[&asmir.id].matches_played, 6);
+        assert_eq!(sb.elo_k_factor, 32.0);
+    }
+
+    #[test]
+    fn v1_achievements_and_match_log_load() {
+        let book: AchievementBook = from_json(FileKind::Achievements, V1_ACHIEVEMENTS).unwrap();
+        assert_eq!(book.unlocks_for("f0016df00784ea6c").len(), 3);
+
+        let records: Vec<MatchRecord> = V1_MATCH_LOG
+            .lines()
+            .map(|line| from_json(FileKind::MatchRecord, line).unwrap())
+            .collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].rounds.len(), 2);
+    }
+
+    #[test]
+    fn v2_files_load() {
+        let state: GameState = from_json(FileKind::GameState, V2_SAVE_STATE).unwrap();
+        assert!(matches!(state.match_format, MatchFormat::FirstTo(3)));
+        assert_eq!(state.rounds.len(), 2);
+        assert!(state.saved_at.is_some());
+
+        let sb: Scoreboard = from_json(FileKind::Scoreboard, V2_SCOREBOARD).unwrap();
+        assert_eq!(sb.profiles.len(), 2);
+    }
+
+    #[test]
+    fn saving_upgrades_to_the_current_version() {
+        let state: GameState = from_json(FileKind::GameState, V1_SAVE_STATE).unwrap();
+        let json = to_json(FileKind::GameState, &state, false).unwrap();
+
+        let envelope: Envelope = serde_json::from_str(&json).unwrap();
+        assert_eq!(envelope.format, "game_state");
+        assert_eq!(envelope.version, FileKind::GameState.current_version());
+
+        let reloaded: GameState = from_json(FileKind::GameState, &json).unwrap();
+        assert_eq!(reloaded.player1.name, state.player1.name);
+    }
+
+    #[test]
+    fn newer_versions_and_other_formats_are_rejected() {
+        let newer = V2_SAVE_STATE.replace("\"version\": 2", "\"version\": 99");
+        let err = from_json::<GameState>(FileKind::GameState, &newer).unwrap_err();
+        assert!(err.contains("version 99"));
+
+        let err = from_json::<GameState>(FileKind::GameState, V2_SCOREBOARD).unwrap_err();
+        assert!(err.contains("expected a game_state file"));
+    }
+}
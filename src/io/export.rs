@@ -0,0 +1,325 @@
+use std::path::Path;
+
+use chrono::{DateTime, Local, Utc};
+use serde::Serialize;
+
+use crate::game::rules::Ruleset;
+use crate::history::match_record::MatchRecord;
+use crate::players::player::Player;
+use crate::scoreboard::scoreboard::Scoreboard;
+use crate::scoreboard::stats::{MatchOutcome, PlayerStats};
+
+/* Export Formats (standings and match history for wikis and spreadsheets) */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    Markdown,
+}
+
+impl ExportFormat {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        match text.to_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            "json" => Ok(ExportFormat::Json),
+            "markdown" | "md" => Ok(ExportFormat::Markdown),
+            _ => Err(format!("Unknown format '{text}' (csv, json or markdown)")),
+        }
+    }
+
+    // Guessed from the output file name (report.csv, standings.md, ...)
+    pub fn from_extension(path: &Path) -> Option<Self> {
+        Self::parse(path.extension()?.to_str()?).ok()
+    }
+}
+
+/* One line of the standings */
+#[derive(Debug, Serialize)]
+pub struct StandingRow {
+    pub player: String,
+    pub matches: u32,
+    pub wins: u32,
+    pub win_rate: f64,
+    pub rounds_won: u32,
+    pub elo_classic: Option<f64>,
+    pub elo_extended: Option<f64>,
+    pub glicko: f64,
+    pub glicko_deviation: f64,
+    // Stats were edited outside the game at some point
+    pub edited: bool,
+}
+
+/* One recorded match */
+#[derive(Debug, Serialize)]
+pub struct MatchRow {
+    pub id: String,
+    pub played_at: DateTime<Utc>,
+    pub player1: String,
+    pub player2: String,
+    pub score_player1: u32,
+    pub score_player2: u32,
+    pub winner: Option<String>,
+    pub ruleset: Ruleset,
+    pub match_format: String,
+    pub rounds: usize,
+}
+
+/* Columns for CSV and Markdown (JSON uses the field names) */
+pub trait TableRow: Serialize {
+    const HEADERS: &'static [&'static str];
+    fn cells(&self) -> Vec<String>;
+}
+
+impl TableRow for StandingRow {
+    const HEADERS: &'static [&'static str] = &[
+        "Player", "Matches", "Wins", "Win %", "Rounds won", "Elo Classic", "Elo Extended", "Glicko-2",
+        "RD",
+    ];
+
+    fn cells(&self) -> Vec<String> {
+        let rating = |r: Option<f64>| r.map(|r| format!("{r:.0}")).unwrap_or_default();
+        let player = if self.edited {
+            format!("{} ⚠", self.player)
+        } else {
+            self.player.clone()
+        };
+
+        vec![
+            player,
+            self.matches.to_string(),
+            self.wins.to_string(),
+            format!("{:.2}", self.win_rate),
+            self.rounds_won.to_string(),
+            rating(self.elo_classic),
+            rating(self.elo_extended),
+            format!("{:.0}", self.glicko),
+            format!("{:.0}", self.glicko_deviation),
+        ]
+    }
+}
+
+impl TableRow for MatchRow {
+    const HEADERS: &'static [&'static str] = &[
+        "ID", "Played", "Player 1", "Player 2", "Score", "Winner", "Ruleset", "Format", "Rounds",
+    ];
+
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.id.clone(),
+            self.played_at.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string(),
+            self.player1.clone(),
+            self.player2.clone(),
+            format!("{}-{}", self.score_player1, self.score_player2),
+            self.winner.clone().unwrap_or_else(|| "tie".to_string()),
+            format!("{:?}", self.ruleset),
+            self.match_format.clone(),
+            self.rounds.to_string(),
+        ]
+    }
+}
+
+/* Standings, most wins first (`player_id` keeps just that player) */
+pub fn standings(scoreboard: &Scoreboard, player_id: Option<&str>) -> Vec<StandingRow> {
+    let mut players: Vec<(&String, &PlayerStats)> = scoreboard
+        .players
+        .iter()
+        .filter(|(id, _)| player_id.is_none_or(|wanted| wanted == id.as_str()))
+        .collect();
+    players.sort_by(|(a_id, a), (b_id, b)| {
+        b.matches_won
+            .cmp(&a.matches_won)
+            .then_with(|| scoreboard.display_name(a_id).cmp(scoreboard.display_name(b_id)))
+    });
+
+    let round = |value: f64| (value * 100.0).round() / 100.0;
+    players
+        .into_iter()
+        .map(|(id, stats)| StandingRow {
+            player: scoreboard.display_name(id).to_string(),
+            matches: stats.matches_played,
+            wins: stats.matches_won,
+            win_rate: if stats.matches_played > 0 {
+                round(stats.matches_won as f64 / stats.matches_played as f64 * 100.0)
+            } else {
+                0.0
+            },
+            rounds_won: stats.rounds_won,
+            elo_classic: stats.ratings.get(&Ruleset::Classic).copied().map(f64::round),
+            elo_extended: stats.ratings.get(&Ruleset::Extended).copied().map(f64::round),
+            glicko: stats.glicko.rating.round(),
+            glicko_deviation: stats.glicko.deviation.round(),
+            edited: stats.tampered,
+        })
+        .collect()
+}
+
+/* Match history rows, oldest first */
+pub fn match_rows(scoreboard: &Scoreboard, records: &[MatchRecord]) -> Vec<MatchRow> {
+    // Current display names, so renamed players show up under one name
+    let name = |player: &Player| {
+        scoreboard
+            .profile_id(player)
+            .map_or(player.name.clone(), |id| scoreboard.display_name(id).to_string())
+    };
+
+    records
+        .iter()
+        .map(|record| MatchRow {
+            id: record.id.clone(),
+            played_at: record.played_at,
+            player1: name(&record.player1),
+            player2: name(&record.player2),
+            score_player1: record.score_player1,
+            score_player2: record.score_player2,
+            winner: match record.outcome {
+                MatchOutcome::Player1Win => Some(name(&record.player1)),
+                MatchOutcome::Player2Win => Some(name(&record.player2)),
+                MatchOutcome::Tie => None,
+            },
+            ruleset: record.ruleset.clone(),
+            match_format: format!("{:?}", record.match_format),
+            rounds: record.rounds.len(),
+        })
+        .collect()
+}
+
+pub fn render<T: TableRow>(rows: &[T], format: ExportFormat) -> Result<String, String> {
+    match format {
+        ExportFormat::Json => serde_json::to_string_pretty(rows)
+            .map(|json| json + "\n")
+            .map_err(|e| format!("Serialize export failed: {e}")),
+        ExportFormat::Csv => {
+            let mut out = csv_line(T::HEADERS.iter().map(|h| h.to_string()));
+            for row in rows {
+                out += &csv_line(row.cells());
+            }
+            Ok(out)
+        }
+        ExportFormat::Markdown => {
+            let mut out = markdown_line(T::HEADERS.iter().map(|h| h.to_string()));
+            out += &markdown_line(T::HEADERS.iter().map(|_| "---".to_string()));
+            for row in rows {
+                out += &markdown_line(row.cells());
+            }
+            Ok(out)
+        }
+    }
+}
+
+// Quotes a field only when it needs it (comma, quote or line break)
+fn csv_line(cells: impl IntoIterator<Item = String>) -> String {
+    let escaped: Vec<String> = cells
+        .into_iter()
+        .map(|cell| {
+            if cell.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", cell.replace('"', "\"\""))
+            } else {
+                cell
+            }
+        })
+        .collect();
+    escaped.join(",") + "\n"
+}
+
+fn markdown_line(cells: impl IntoIterator<Item = String>) -> String {
+    let escaped: Vec<String> = cells.into_iter().map(|cell| cell.replace('|', "\\|")).collect();
+    format!("| {} |\n", escaped.join(" | "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::game_state::GameState;
+    use crate::game::match_settings::MatchFormat;
+    use crate::game::round::{resolve_round, RoundResult, Throw};
+    use crate::game::rules::Gesture;
+
+    // One-round match, Player 1 throws `g1`
+    fn played(scoreboard: &mut Scoreboard, p1: &str, p2: &str, g1: Gesture, g2: Gesture) -> MatchRecord {
+        let mut state = GameState::new(
+            Player::new_human(p1),
+            Player::new_human(p2),
+            Ruleset::Classic,
+            MatchFormat::SingleRound,
+            None,
+            None,
+        );
+        let throw = |gesture| Throw { gesture, reaction_ms: None };
+        let outcome = match resolve_round(&mut state, throw(g1), throw(g2)) {
+            RoundResult::Player1Win => MatchOutcome::Player1Win,
+            RoundResult::Player2Win => MatchOutcome::Player2Win,
+            RoundResult::Tie => MatchOutcome::Tie,
+        };
+        let record = MatchRecord::from_state(&state, outcome);
+        scoreboard.update_after_match(&record);
+        record
+    }
+
+    #[test]
+    fn formats_parse_from_names_and_extensions() {
+        assert_eq!(ExportFormat::parse("CSV"), Ok(ExportFormat::Csv));
+        assert_eq!(ExportFormat::parse("md"), Ok(ExportFormat::Markdown));
+        assert!(ExportFormat::parse("xml").is_err());
+        assert_eq!(ExportFormat::from_extension(Path::new("out/standings.json")), Some(ExportFormat::Json));
+        assert_eq!(ExportFormat::from_extension(Path::new("standings")), None);
+    }
+
+    #[test]
+    fn standings_go_by_wins_then_name() {
+        let mut scoreboard = Scoreboard::new();
+        played(&mut scoreboard, "Cid", "Ana", Gesture::Rock, Gesture::Scissors);
+        played(&mut scoreboard, "Ana", "Ben", Gesture::Rock, Gesture::Scissors);
+        played(&mut scoreboard, "Ben", "Cid", Gesture::Rock, Gesture::Scissors);
+        played(&mut scoreboard, "Ana", "Ben", Gesture::Rock, Gesture::Rock);
+
+        let rows = standings(&scoreboard, None);
+        let order: Vec<&str> = rows.iter().map(|r| r.player.as_str()).collect();
+        assert_eq!(order, ["Ana", "Ben", "Cid"]);
+        assert_eq!((rows[0].matches, rows[0].wins, rows[0].win_rate), (3, 1, 33.33));
+        assert_eq!(rows[0].elo_extended, None);
+
+        let ben = scoreboard.find_profile("Ben").unwrap().id.clone();
+        let only_ben = standings(&scoreboard, Some(&ben));
+        assert_eq!(only_ben.len(), 1);
+        assert_eq!(only_ben[0].player, "Ben");
+    }
+
+    #[test]
+    fn csv_and_markdown_escape_their_separators() {
+        let mut scoreboard = Scoreboard::new();
+        played(&mut scoreboard, "Smith, \"Ace\"", "A|B", Gesture::Paper, Gesture::Rock);
+        let ace = scoreboard.find_profile("Smith, \"Ace\"").unwrap().id.clone();
+        scoreboard.flag_edited(vec![ace]);
+        let rows = standings(&scoreboard, None);
+
+        let csv = render(&rows, ExportFormat::Csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "Player,Matches,Wins,Win %,Rounds won,Elo Classic,Elo Extended,Glicko-2,RD");
+        assert_eq!(lines[1], "\"Smith, \"\"Ace\"\" ⚠\",1,1,100.00,1,1216,,1500,350");
+
+        let markdown = render(&rows, ExportFormat::Markdown).unwrap();
+        let lines: Vec<&str> = markdown.lines().collect();
+        assert!(lines[1].starts_with("| --- | --- |"));
+        assert!(lines[3].starts_with("| A\\|B | 1 | 0 | 0.00 |"));
+    }
+
+    #[test]
+    fn match_history_uses_current_names_and_json_field_names() {
+        let mut scoreboard = Scoreboard::new();
+        let records = [
+            played(&mut scoreboard, "Ana", "Ben", Gesture::Rock, Gesture::Paper),
+            played(&mut scoreboard, "Ana", "Ben", Gesture::Rock, Gesture::Rock),
+        ];
+        scoreboard.rename_player("Ben", "Benjamin").unwrap();
+
+        let rows = match_rows(&scoreboard, &records);
+        assert_eq!(rows[0].winner.as_deref(), Some("Benjamin"));
+        assert_eq!(rows[1].cells()[5], "tie");
+        assert_eq!(rows[1].cells()[4], "0-0");
+
+        let json: serde_json::Value = serde_json::from_str(&render(&rows, ExportFormat::Json).unwrap()).unwrap();
+        assert_eq!(json[0]["player2"], "Benjamin");
+        assert_eq!(json[0]["match_format"], "SingleRound");
+        assert_eq!(json[1]["winner"], serde_json::Value::Null);
+    }
+}
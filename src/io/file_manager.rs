@@ -1,25 +1,108 @@
 use std::fs;
 use std::io::{Read, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-pub fn ensure_saves_dir() -> std::io::Result<()> {
-    fs::create_dir_all("saves")?;
-    Ok(())
-}
+/* How many older versions of each file are kept (`<file>.bak1` is the newest) */
+pub const BACKUP_GENERATIONS: usize = 3;
 
-pub fn write_to_file(path: &str, contents: &str) -> std::io::Result<()> {
-    if let Some(parent) = Path::new(path).parent() {
+/* Replaces `path` without ever leaving it half-written, keeping the previous versions as backups */
+pub fn write_to_file(path: impl AsRef<Path>, contents: &str) -> std::io::Result<()> {
+    let path = path.as_ref();
+    if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
     }
 
-    let mut file = fs::File::create(path)?;
+    rotate_backups(path)?;
+    write_atomic(path, contents)
+}
+
+/* Temp file >> fsync >> rename over the target (a crash leaves either the old or the new file) */
+pub fn write_atomic(path: impl AsRef<Path>, contents: &str) -> std::io::Result<()> {
+    let path = path.as_ref();
+    let tmp = sibling(path, ".tmp");
+
+    let mut file = fs::File::create(&tmp)?;
     file.write_all(contents.as_bytes())?;
+    file.sync_all()?;
+    drop(file);
+
+    fs::rename(&tmp, path)?;
+    sync_parent_dir(path);
+    Ok(())
+}
+
+/* Backups of `path`, newest first (only the ones that exist) */
+pub fn backup_paths(path: impl AsRef<Path>) -> Vec<PathBuf> {
+    (1..=BACKUP_GENERATIONS)
+        .map(|generation| backup_path(path.as_ref(), generation))
+        .filter(|p| p.exists())
+        .collect()
+}
+
+/* Deletes `path` and its backups (missing files are fine) */
+pub fn remove_with_backups(path: impl AsRef<Path>) -> std::io::Result<()> {
+    let path = path.as_ref();
+    let files = std::iter::once(path.to_path_buf())
+        .chain((1..=BACKUP_GENERATIONS).map(|generation| backup_path(path, generation)));
+
+    for file in files {
+        match fs::remove_file(&file) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+    }
     Ok(())
 }
 
-pub fn read_from_file(path: &str) -> std::io::Result<String> {
+pub fn read_from_file(path: impl AsRef<Path>) -> std::io::Result<String> {
     let mut file = fs::File::open(path)?;
     let mut contents = String::new();
     file.read_to_string(&mut contents)?;
     Ok(contents)
 }
+
+pub fn append_line(path: impl AsRef<Path>, line: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.as_ref().parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut file = fs::OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")?;
+    file.sync_all()?;
+    Ok(())
+}
+
+// bak2 >> bak3, bak1 >> bak2, then a copy of the current file becomes bak1
+fn rotate_backups(path: &Path) -> std::io::Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+
+    for generation in (1..BACKUP_GENERATIONS).rev() {
+        let older = backup_path(path, generation);
+        if older.exists() {
+            fs::rename(&older, backup_path(path, generation + 1))?;
+        }
+    }
+    fs::copy(path, backup_path(path, 1))?;
+    Ok(())
+}
+
+fn backup_path(path: &Path, generation: usize) -> PathBuf {
+    sibling(path, &format!(".bak{generation}"))
+}
+
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+// Makes the rename itself durable (not possible on every platform, so best effort)
+fn sync_parent_dir(path: &Path) {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty())
+        && let Ok(dir) = fs::File::open(parent)
+    {
+        let _ = dir.sync_all();
+    }
+}
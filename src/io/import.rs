@@ -0,0 +1,240 @@
+use std::collections::{HashMap, HashSet};
+use std::io::ErrorKind;
+use std::path::Path;
+
+use crate::history::match_record::MatchRecord;
+use crate::io::envelope::{FileKind, from_json};
+use crate::io::file_manager::read_from_file;
+use crate::scoreboard::scoreboard::Scoreboard;
+
+const SCOREBOARD_FILE: &str = "scoreboard.json";
+const MATCH_LOG_FILE: &str = "match_log.jsonl";
+
+/* Another Installation's Saves (its scoreboard, its match log, or a whole data directory)
+ * Their signatures were made with the other machine's secret, so they can't be checked here -
+ * importing means trusting that installation.
+*/
+pub struct ImportSource {
+    pub scoreboard: Option<Scoreboard>,
+    pub matches: Option<Vec<MatchRecord>>,
+
+    // Match log lines that didn't parse
+    pub skipped_lines: usize,
+}
+
+/* A directory is searched for scoreboard.json and match_log.jsonl, a `.jsonl` file is a match log,
+ * anything else is read as a scoreboard.
+*/
+pub fn read_source(path: &Path) -> Result<ImportSource, String> {
+    let mut source = ImportSource {
+        scoreboard: None,
+        matches: None,
+        skipped_lines: 0,
+    };
+
+    if path.is_dir() {
+        source.scoreboard = read_optional(&path.join(SCOREBOARD_FILE))?
+            .map(|text| parse_scoreboard(&text))
+            .transpose()?;
+        if let Some(text) = read_optional(&path.join(MATCH_LOG_FILE))? {
+            source.read_match_log(&text);
+        }
+        if source.scoreboard.is_none() && source.matches.is_none() {
+            return Err(format!(
+                "No {SCOREBOARD_FILE} or {MATCH_LOG_FILE} in {}",
+                path.display()
+            ));
+        }
+        return Ok(source);
+    }
+
+    let text = read_from_file(path).map_err(|e| format!("Read {} failed: {e}", path.display()))?;
+    if path.extension().is_some_and(|ext| ext == "jsonl") {
+        source.read_match_log(&text);
+    } else {
+        source.scoreboard = Some(parse_scoreboard(&text)?);
+    }
+    Ok(source)
+}
+
+impl ImportSource {
+    fn read_match_log(&mut self, text: &str) {
+        let mut records = Vec::new();
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            match from_json::<MatchRecord>(FileKind::MatchRecord, line) {
+                Ok(record) => records.push(record),
+                Err(_) => self.skipped_lines += 1,
+            }
+        }
+        self.matches = Some(records);
+    }
+}
+
+fn read_optional(path: &Path) -> Result<Option<String>, String> {
+    match read_from_file(path) {
+        Ok(text) => Ok(Some(text)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("Read {} failed: {e}", path.display())),
+    }
+}
+
+fn parse_scoreboard(text: &str) -> Result<Scoreboard, String> {
+    from_json(FileKind::Scoreboard, text).map_err(|e| format!("Parse scoreboard failed: {e}"))
+}
+
+/* How the stats were merged */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeMode {
+    // Everything rebuilt by replaying both match logs
+    Replayed,
+    // The local scoreboard counts matches from before its match log, so new matches go on top of it
+    AddedOnTop,
+    // No match log to go by: stats added up player by player
+    AddedUp,
+}
+
+/* What an import would do - nothing is saved until the caller applies it */
+pub struct ImportPlan {
+    pub scoreboard: Scoreboard,
+
+    // Matches to append to the local log (oldest first)
+    pub new_matches: Vec<MatchRecord>,
+    pub duplicate_matches: usize,
+
+    // (their name, our name) for players that already had a profile here
+    pub matched_players: Vec<(String, String)>,
+    pub new_players: Vec<String>,
+
+    pub mode: MergeMode,
+
+    // Their scoreboard counts matches their log doesn't have - only players without
+    // any logged match bring those over
+    pub missing_matches: bool,
+}
+
+/* Merges `source` into the local scoreboard and match log
+ * Their matches are deduplicated by match ID. When the local log accounts for every local match,
+ * stats and ratings are rebuilt by replaying both logs in order; otherwise the new matches are
+ * played on top of the local scoreboard so nothing from before the log gets lost.
+ * Players without logged matches keep their scoreboard stats either way.
+*/
+pub fn plan_import(
+    local: &Scoreboard,
+    local_matches: &[MatchRecord],
+    source: ImportSource,
+) -> ImportPlan {
+    let mut merged = local.clone();
+    merged.tampering = None;
+    let foreign = source.scoreboard.unwrap_or_else(Scoreboard::new);
+    let ids = merged.adopt_profiles(&foreign);
+
+    let mut matched_players = Vec::new();
+    let mut new_players = Vec::new();
+    for (theirs, ours) in &ids {
+        let their_name = foreign.display_name(theirs).to_string();
+        if local.profiles.contains_key(ours) {
+            matched_players.push((their_name, local.display_name(ours).to_string()));
+        } else {
+            new_players.push(their_name);
+        }
+    }
+    matched_players.sort();
+    new_players.sort();
+
+    let Some(their_matches) = source.matches else {
+        merged.absorb_stats(&foreign, &ids);
+        return ImportPlan {
+            scoreboard: merged,
+            new_matches: Vec::new(),
+            duplicate_matches: 0,
+            matched_players,
+            new_players,
+            mode: MergeMode::AddedUp,
+            missing_matches: false,
+        };
+    };
+
+    let their_ids: HashSet<String> = their_matches.iter().map(|r| r.id.clone()).collect();
+    let mut seen: HashSet<String> = local_matches.iter().map(|r| r.id.clone()).collect();
+    let total = their_matches.len();
+    let mut new_matches: Vec<MatchRecord> = their_matches
+        .into_iter()
+        .filter(|record| seen.insert(record.id.clone()))
+        .map(|record| with_local_ids(record, &ids, &merged))
+        .collect();
+    new_matches.sort_by_key(|r| r.played_at);
+    let duplicate_matches = total - new_matches.len();
+
+    let all: Vec<MatchRecord> = local_matches.iter().chain(&new_matches).cloned().collect();
+    let replayed = merged.replayed(&all);
+    let logged: HashSet<String> = replayed.players.keys().cloned().collect();
+
+    let (mut rebuilt, mode) = if log_is_complete(&merged, local_matches, &logged) {
+        let mut rebuilt = replayed;
+        keep_unlogged_stats(&mut rebuilt, &merged, &logged);
+        (rebuilt, MergeMode::Replayed)
+    } else {
+        let mut rebuilt = merged.clone();
+        for record in &new_matches {
+            rebuilt.update_after_match(record);
+        }
+        (rebuilt, MergeMode::AddedOnTop)
+    };
+
+    // Their players with no logged matches
+    let mut theirs = merged.clone();
+    theirs.players.clear();
+    theirs.absorb_stats(&foreign, &ids);
+    keep_unlogged_stats(&mut rebuilt, &theirs, &logged);
+    rebuilt.keep_tamper_flags(&theirs);
+
+    let their_log: Vec<MatchRecord> = all.into_iter().filter(|r| their_ids.contains(&r.id)).collect();
+    let their_players: HashSet<String> = theirs.players.keys().cloned().collect();
+    let missing_matches = !log_is_complete(&theirs, &their_log, &their_players);
+
+    ImportPlan {
+        scoreboard: rebuilt,
+        new_matches,
+        duplicate_matches,
+        matched_players,
+        new_players,
+        mode,
+        missing_matches,
+    }
+}
+
+// Their profile IDs >> ours (an ID neither side knows is dropped, so the name decides)
+fn with_local_ids(
+    mut record: MatchRecord,
+    ids: &HashMap<String, String>,
+    merged: &Scoreboard,
+) -> MatchRecord {
+    for player in [&mut record.player1, &mut record.player2] {
+        player.profile_id = player.profile_id.as_ref().and_then(|id| {
+            ids.get(id)
+                .cloned()
+                .or_else(|| merged.profiles.contains_key(id).then(|| id.clone()))
+        });
+    }
+    record
+}
+
+// The log holds every match the scoreboard counts for these players
+fn log_is_complete(
+    scoreboard: &Scoreboard,
+    matches: &[MatchRecord],
+    players: &HashSet<String>,
+) -> bool {
+    let replayed = scoreboard.replayed(matches);
+    let played = |sb: &Scoreboard, id: &String| sb.players.get(id).map_or(0, |s| s.matches_played);
+    players
+        .iter()
+        .all(|id| played(scoreboard, id) == played(&replayed, id))
+}
+
+// Players from `side` (keyed by our IDs) who have no logged matches come over with their saved stats
+fn keep_unlogged_stats(rebuilt: &mut Scoreboard, side: &Scoreboard, logged: &HashSet<String>) {
+    let mut unlogged = side.clone();
+    unlogged.players.retain(|id, _| !logged.contains(id));
+    rebuilt.absorb_stats(&unlogged, &HashMap::new());
+}
@@ -0,0 +1,151 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::Sha256;
+
+use crate::io::file_manager::{read_from_file, write_atomic};
+
+/* Tamper Detection (keeps the leaderboard honest - not DRM)
+ * Saved files carry HMAC-SHA256 signatures keyed by a secret that never leaves this machine.
+ * Editing a file by hand breaks its signature; the game notices on the next load.
+*/
+pub const SECRET_FILE: &str = "secret.key";
+const SECRET_BYTES: usize = 32;
+
+pub struct SecretKey {
+    bytes: Vec<u8>,
+}
+
+impl SecretKey {
+    /* Reads `<dir>/secret.key` >> (key, true if it was just generated) */
+    pub fn load_or_create(dir: &Path) -> Result<(Self, bool), String> {
+        let path = dir.join(SECRET_FILE);
+        match read_from_file(&path) {
+            Ok(text) => {
+                let bytes = from_hex(text.trim())
+                    .filter(|b| b.len() == SECRET_BYTES)
+                    .ok_or_else(|| format!("Read secret failed: {} is damaged", path.display()))?;
+                return Ok((Self { bytes }, false));
+            }
+            Err(e) if e.kind() != ErrorKind::NotFound => {
+                return Err(format!("Read secret failed: {e}"));
+            }
+            Err(_) => {}
+        }
+
+        let mut bytes = vec![0u8; SECRET_BYTES];
+        rand::rngs::OsRng.fill_bytes(&mut bytes);
+
+        std::fs::create_dir_all(dir).map_err(|e| format!("Create data dir failed: {e}"))?;
+        write_atomic(&path, &to_hex(&bytes)).map_err(|e| format!("Write secret failed: {e}"))?;
+        restrict_permissions(&path);
+        Ok((Self { bytes }, true))
+    }
+
+    fn sign(&self, parts: &[&str]) -> String {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&self.bytes).expect("HMAC accepts any key length");
+        for part in parts {
+            mac.update(part.as_bytes());
+            mac.update(&[0]);
+        }
+        to_hex(&mac.finalize().into_bytes())
+    }
+}
+
+/* Signatures stored in a file's envelope: one over all the data, one per entry (e.g. per player) */
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Seal {
+    pub data: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub entries: BTreeMap<String, String>,
+}
+
+/* What a signature check found */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Integrity {
+    Intact,
+    // No signature at all (written before tamper detection, or stripped by hand)
+    Unsigned,
+    // Changed outside the game - the entries whose own signature broke (may be empty)
+    Tampered(Vec<String>),
+}
+
+pub fn seal(
+    key: &SecretKey,
+    format: &str,
+    version: u32,
+    data: &Value,
+    entries: &[(String, &Value)],
+) -> Seal {
+    Seal {
+        data: key.sign(&[format, &version.to_string(), "", &data.to_string()]),
+        entries: entries
+            .iter()
+            .map(|(name, value)| {
+                let signature = key.sign(&[format, &version.to_string(), name, &value.to_string()]);
+                (name.clone(), signature)
+            })
+            .collect(),
+    }
+}
+
+pub fn check(
+    key: &SecretKey,
+    format: &str,
+    version: u32,
+    data: &Value,
+    entries: &[(String, &Value)],
+    seal_found: Option<&Seal>,
+) -> Integrity {
+    let Some(found) = seal_found else {
+        return Integrity::Unsigned;
+    };
+    let expected = seal(key, format, version, data, entries);
+
+    let mut tampered: Vec<String> = expected
+        .entries
+        .iter()
+        .filter(|(name, signature)| found.entries.get(*name) != Some(signature))
+        .map(|(name, _)| name.clone())
+        .collect();
+    tampered.sort();
+
+    if expected.data == found.data && tampered.is_empty() {
+        Integrity::Intact
+    } else {
+        Integrity::Tampered(tampered)
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, b| {
+        let _ = write!(hex, "{b:02x}");
+        hex
+    })
+}
+
+fn from_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(unix)]
+fn restrict_permissions(path: &Path) {
+    use std::os::unix::fs::PermissionsExt;
+    let _ = std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600));
+}
+
+#[cfg(not(unix))]
+fn restrict_permissions(_path: &Path) {}
@@ -0,0 +1,416 @@
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::achievements::tracker::AchievementBook;
+use crate::game::game_state::GameState;
+use crate::history::match_record::MatchRecord;
+use crate::io::envelope::{
+    FileKind, from_json, from_signed_json, is_sealed, to_json, to_signed_json,
+};
+use crate::io::file_manager::{
+    append_line, backup_paths, read_from_file, remove_with_backups, write_atomic, write_to_file,
+};
+use crate::io::integrity::{Integrity, SECRET_FILE, SecretKey};
+use crate::io::save_load::LEGACY_SLOT;
+use crate::io::storage::{Storage, check_save_name};
+use crate::scoreboard::scoreboard::Scoreboard;
+
+const SAVE_STATE_PREFIX: &str = "save_state";
+const SCOREBOARD_FILE: &str = "scoreboard.json";
+const MATCH_LOG_FILE: &str = "match_log.jsonl";
+const ACHIEVEMENTS_FILE: &str = "achievements.json";
+
+/* JSON Files in One Directory (the original save format) */
+pub struct JsonStorage {
+    dir: PathBuf,
+
+    // Signs and checks every file - None if the secret can't be read (tamper detection is off)
+    key: Option<SecretKey>,
+}
+
+impl JsonStorage {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        let dir = dir.into();
+        let (key, created) = match SecretKey::load_or_create(&dir) {
+            Ok((key, created)) => (Some(key), created),
+            Err(e) => {
+                eprintln!("Warning: {e} - tamper detection is off");
+                (None, false)
+            }
+        };
+
+        let storage = Self { dir, key };
+        if created {
+            storage.sign_existing_files();
+        }
+        storage
+    }
+
+    fn path(&self, file: &str) -> PathBuf {
+        self.dir.join(file)
+    }
+
+    /* The legacy slot keeps the original `save_state.json`, other slots get `save_state.<name>.json` */
+    fn game_state_path(&self, name: &str) -> Result<PathBuf, String> {
+        check_save_name(name)?;
+        Ok(if name == LEGACY_SLOT {
+            self.path(&format!("{SAVE_STATE_PREFIX}.json"))
+        } else {
+            self.path(&format!("{SAVE_STATE_PREFIX}.{name}.json"))
+        })
+    }
+
+    fn serialize<T: Serialize>(
+        &self,
+        kind: FileKind,
+        value: &T,
+        pretty: bool,
+    ) -> Result<String, String> {
+        match &self.key {
+            Some(key) => to_signed_json(kind, value, pretty, key),
+            None => to_json(kind, value, pretty),
+        }
+        .map_err(|e| format!("Serialize {} failed: {e}", kind.label()))
+    }
+
+    // Without a key there is nothing to check against, so everything counts as intact
+    fn deserialize<T: DeserializeOwned>(
+        &self,
+        kind: FileKind,
+        text: &str,
+    ) -> Result<(T, Integrity), String> {
+        match &self.key {
+            Some(key) => from_signed_json(kind, text, key),
+            None => from_json(kind, text).map(|value| (value, Integrity::Intact)),
+        }
+        .map_err(|e| format!("Parse {} failed: {e}", kind.label()))
+    }
+
+    /* A file that can't be read or parsed is replaced by its newest backup that can (with a warning) */
+    fn read_json<T: DeserializeOwned>(
+        &self,
+        path: &Path,
+        kind: FileKind,
+    ) -> Result<Option<(T, Integrity)>, String> {
+        let error = match self.parse_json_file(path, kind) {
+            Ok(value) => return Ok(Some(value)),
+            Err(None) => return Ok(None),
+            Err(Some(e)) => e,
+        };
+
+        for backup in backup_paths(path) {
+            let Ok(value) = self.parse_json_file::<T>(&backup, kind) else {
+                continue;
+            };
+            eprintln!(
+                "Warning: {error} - restored {} from {}",
+                path.display(),
+                backup.display()
+            );
+            // Put the good copy back without rotating the broken file into the backups
+            if let Ok(contents) = read_from_file(&backup) {
+                let _ = write_atomic(path, &contents);
+            }
+            return Ok(Some(value));
+        }
+
+        Err(error)
+    }
+
+    // Err(None) when there is no such file
+    fn parse_json_file<T: DeserializeOwned>(
+        &self,
+        path: &Path,
+        kind: FileKind,
+    ) -> Result<(T, Integrity), Option<String>> {
+        let contents = match read_from_file(path) {
+            Ok(s) => s,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Err(None),
+            Err(e) => return Err(Some(format!("Read {} failed: {e}", kind.label()))),
+        };
+        self.deserialize(kind, &contents).map_err(Some)
+    }
+
+    /* Newest backup whose signatures still check out - it becomes the file again */
+    fn restore_backup<T: DeserializeOwned>(
+        &self,
+        path: &Path,
+        kind: FileKind,
+    ) -> Result<Option<T>, String> {
+        if self.key.is_none() {
+            return Ok(None);
+        }
+
+        for backup in backup_paths(path) {
+            let Ok(text) = read_from_file(&backup) else {
+                continue;
+            };
+            if let Ok((value, Integrity::Intact)) = self.deserialize::<T>(kind, &text) {
+                write_atomic(path, &text)
+                    .map_err(|e| format!("Restore {} failed: {e}", kind.label()))?;
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+
+    /* Every log line that parses, with whether its signature checks out
+     * A line that doesn't parse (e.g. cut short by a crash) is skipped instead of losing the whole log.
+     */
+    fn read_match_log(&self) -> Result<Vec<(MatchRecord, bool)>, String> {
+        let contents = match read_from_file(self.path(MATCH_LOG_FILE)) {
+            Ok(s) => s,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(format!("Read match log failed: {e}")),
+        };
+
+        Ok(contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| self.deserialize(FileKind::MatchRecord, line).ok())
+            .map(|(record, integrity)| (record, integrity == Integrity::Intact))
+            .collect())
+    }
+
+    /* Rewrites the log with its edited entries signed as they are now (`accept`) or left out */
+    fn settle_tampered_matches(&self, accept: bool) -> Result<(), String> {
+        let path = self.path(MATCH_LOG_FILE);
+        let contents = match read_from_file(&path) {
+            Ok(s) => s,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(format!("Read match log failed: {e}")),
+        };
+
+        let mut lines = Vec::new();
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            match self.deserialize::<MatchRecord>(FileKind::MatchRecord, line) {
+                Ok((_, Integrity::Intact)) | Err(_) => lines.push(line.to_string()),
+                Ok((record, _)) if accept => {
+                    lines.push(self.serialize(FileKind::MatchRecord, &record, false)?)
+                }
+                Ok(_) => {}
+            }
+        }
+
+        // The log as it was stays behind as the newest backup
+        write_to_file(&path, &(lines.join("\n") + "\n"))
+            .map_err(|e| format!("Write match log failed: {e}"))
+    }
+
+    fn write_json<T: Serialize>(
+        &self,
+        path: &Path,
+        value: &T,
+        kind: FileKind,
+    ) -> Result<(), String> {
+        let json = self.serialize(kind, value, true)?;
+        write_to_file(path, &json).map_err(|e| format!("Write {} file failed: {e}", kind.label()))
+    }
+
+    /* New secret: sign the files from before tamper detection, so they stay trusted
+     * Files that already carry a seal were signed with a key that's gone now - they're left alone,
+     * so they show up as changed instead of a deleted secret.key quietly approving any edit.
+     */
+    fn sign_existing_files(&self) {
+        let mut files = vec![
+            (self.path(SCOREBOARD_FILE), FileKind::Scoreboard),
+            (self.path(ACHIEVEMENTS_FILE), FileKind::Achievements),
+        ];
+        for name in self.list_game_states().unwrap_or_default() {
+            if let Ok(path) = self.game_state_path(&name) {
+                files.push((path, FileKind::GameState));
+            }
+        }
+
+        let mut sealed = 0;
+        for (path, kind) in files {
+            let Ok(text) = read_from_file(&path) else {
+                continue;
+            };
+            if is_sealed(kind, &text) {
+                sealed += 1;
+                continue;
+            }
+            let signed =
+                from_json::<Value>(kind, &text).and_then(|data| self.serialize(kind, &data, true));
+            if let Ok(json) = signed {
+                let _ = write_atomic(&path, &json);
+            }
+        }
+
+        let log = self.path(MATCH_LOG_FILE);
+        if let Ok(text) = read_from_file(&log) {
+            let lines: Vec<String> = text
+                .lines()
+                .filter_map(|line| {
+                    if is_sealed(FileKind::MatchRecord, line) {
+                        sealed += 1;
+                        return Some(line.to_string());
+                    }
+                    let record = from_json::<Value>(FileKind::MatchRecord, line).ok()?;
+                    self.serialize(FileKind::MatchRecord, &record, false).ok()
+                })
+                .collect();
+            let _ = write_atomic(&log, &(lines.join("\n") + "\n"));
+        }
+
+        if sealed > 0 {
+            eprintln!(
+                "Warning: {} was missing and has been recreated - files signed with the old one \
+                 will show as changed outside the game",
+                self.path(SECRET_FILE).display()
+            );
+        }
+    }
+}
+
+/* Everything a tampered file could point at (the check itself can't tell which player changed) */
+fn all_players<T>(players: &HashMap<String, T>) -> Vec<String> {
+    let mut ids: Vec<String> = players.keys().cloned().collect();
+    ids.sort();
+    ids
+}
+
+/* The players a signature check pins an edit on (None if the file is intact) */
+fn tampered_players<T>(integrity: Integrity, players: &HashMap<String, T>) -> Option<Vec<String>> {
+    match integrity {
+        Integrity::Intact => None,
+        Integrity::Unsigned => Some(all_players(players)),
+        // Only top-level data changed (records, K-factor, ...) - that can't be pinned on anyone
+        Integrity::Tampered(ids) if ids.is_empty() => Some(all_players(players)),
+        Integrity::Tampered(ids) => Some(ids),
+    }
+}
+
+impl Storage for JsonStorage {
+    fn load_scoreboard(&self) -> Result<Option<Scoreboard>, String> {
+        let Some((mut sb, integrity)) =
+            self.read_json::<Scoreboard>(&self.path(SCOREBOARD_FILE), FileKind::Scoreboard)?
+        else {
+            return Ok(None);
+        };
+
+        sb.tampering = tampered_players(integrity, &sb.players);
+        Ok(Some(sb))
+    }
+
+    fn save_scoreboard(&self, scoreboard: &Scoreboard) -> Result<(), String> {
+        self.write_json(
+            &self.path(SCOREBOARD_FILE),
+            scoreboard,
+            FileKind::Scoreboard,
+        )
+    }
+
+    fn restore_scoreboard_backup(&self) -> Result<Option<Scoreboard>, String> {
+        self.restore_backup(&self.path(SCOREBOARD_FILE), FileKind::Scoreboard)
+    }
+
+    fn list_game_states(&self) -> Result<Vec<String>, String> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(format!("List saves failed: {e}")),
+        };
+
+        let mut names: Vec<String> = entries
+            .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+            .filter_map(|file| {
+                let rest = file
+                    .strip_prefix(SAVE_STATE_PREFIX)?
+                    .strip_suffix(".json")?;
+                match rest.strip_prefix('.') {
+                    Some(name) => Some(name.to_string()),
+                    None if rest.is_empty() => Some(LEGACY_SLOT.to_string()),
+                    None => None,
+                }
+            })
+            .filter(|name| check_save_name(name).is_ok())
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    fn load_game_state(&self, name: &str) -> Result<Option<GameState>, String> {
+        let loaded =
+            self.read_json::<GameState>(&self.game_state_path(name)?, FileKind::GameState)?;
+        Ok(loaded.map(|(mut state, integrity)| {
+            state.tampered = integrity != Integrity::Intact;
+            state
+        }))
+    }
+
+    fn save_game_state(&self, name: &str, state: &GameState) -> Result<(), String> {
+        self.write_json(&self.game_state_path(name)?, state, FileKind::GameState)
+    }
+
+    fn delete_game_state(&self, name: &str) -> Result<(), String> {
+        remove_with_backups(self.game_state_path(name)?)
+            .map_err(|e| format!("Delete state failed: {e}"))
+    }
+
+    fn restore_game_state_backup(&self, name: &str) -> Result<Option<GameState>, String> {
+        self.restore_backup(&self.game_state_path(name)?, FileKind::GameState)
+    }
+
+    /* Append-Only Log >> one JSON match per line, so a finished match never rewrites older ones */
+    fn append_match(&self, record: &MatchRecord) -> Result<(), String> {
+        let line = self.serialize(FileKind::MatchRecord, record, false)?;
+        append_line(self.path(MATCH_LOG_FILE), &line)
+            .map_err(|e| format!("Write match log failed: {e}"))
+    }
+
+    /* Entries edited by hand are left out (the main menu asks what to do with them) */
+    fn load_matches(&self) -> Result<Vec<MatchRecord>, String> {
+        let log = self.read_match_log()?;
+        let tampered = log.iter().filter(|(_, intact)| !intact).count();
+        if tampered > 0 {
+            eprintln!(
+                "Warning: skipped {tampered} match log entries changed outside the game \
+                 - start the game to review them"
+            );
+        }
+        Ok(log
+            .into_iter()
+            .filter_map(|(record, intact)| intact.then_some(record))
+            .collect())
+    }
+
+    fn load_tampered_matches(&self) -> Result<Vec<MatchRecord>, String> {
+        Ok(self
+            .read_match_log()?
+            .into_iter()
+            .filter_map(|(record, intact)| (!intact).then_some(record))
+            .collect())
+    }
+
+    fn remove_tampered_matches(&self) -> Result<(), String> {
+        self.settle_tampered_matches(false)
+    }
+
+    fn accept_tampered_matches(&self) -> Result<(), String> {
+        self.settle_tampered_matches(true)
+    }
+
+    fn load_achievements(&self) -> Result<Option<AchievementBook>, String> {
+        let loaded = self
+            .read_json::<AchievementBook>(&self.path(ACHIEVEMENTS_FILE), FileKind::Achievements)?;
+        Ok(loaded.map(|(mut book, integrity)| {
+            book.tampering = tampered_players(integrity, &book.players);
+            book
+        }))
+    }
+
+    fn save_achievements(&self, book: &AchievementBook) -> Result<(), String> {
+        self.write_json(&self.path(ACHIEVEMENTS_FILE), book, FileKind::Achievements)
+    }
+
+    fn restore_achievements_backup(&self) -> Result<Option<AchievementBook>, String> {
+        self.restore_backup(&self.path(ACHIEVEMENTS_FILE), FileKind::Achievements)
+    }
+}
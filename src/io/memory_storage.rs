@@ -0,0 +1,75 @@
+use std::collections::BTreeMap;
+use std::sync::{Mutex, MutexGuard};
+
+use crate::achievements::tracker::AchievementBook;
+use crate::game::game_state::GameState;
+use crate::history::match_record::MatchRecord;
+use crate::io::storage::Storage;
+use crate::scoreboard::scoreboard::Scoreboard;
+
+/* Keeps Everything in Memory - nothing survives the process (guest sessions, trying things out) */
+#[derive(Default)]
+pub struct MemoryStorage {
+    data: Mutex<MemoryData>,
+}
+
+#[derive(Default)]
+struct MemoryData {
+    scoreboard: Option<Scoreboard>,
+    game_states: BTreeMap<String, GameState>,
+    matches: Vec<MatchRecord>,
+    achievements: Option<AchievementBook>,
+}
+
+impl MemoryStorage {
+    fn data(&self) -> Result<MutexGuard<'_, MemoryData>, String> {
+        self.data.lock().map_err(|_| "Storage lock poisoned".to_string())
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn load_scoreboard(&self) -> Result<Option<Scoreboard>, String> {
+        Ok(self.data()?.scoreboard.clone())
+    }
+
+    fn save_scoreboard(&self, scoreboard: &Scoreboard) -> Result<(), String> {
+        self.data()?.scoreboard = Some(scoreboard.clone());
+        Ok(())
+    }
+
+    fn list_game_states(&self) -> Result<Vec<String>, String> {
+        Ok(self.data()?.game_states.keys().cloned().collect())
+    }
+
+    fn load_game_state(&self, name: &str) -> Result<Option<GameState>, String> {
+        Ok(self.data()?.game_states.get(name).cloned())
+    }
+
+    fn save_game_state(&self, name: &str, state: &GameState) -> Result<(), String> {
+        self.data()?.game_states.insert(name.to_string(), state.clone());
+        Ok(())
+    }
+
+    fn delete_game_state(&self, name: &str) -> Result<(), String> {
+        self.data()?.game_states.remove(name);
+        Ok(())
+    }
+
+    fn append_match(&self, record: &MatchRecord) -> Result<(), String> {
+        self.data()?.matches.push(record.clone());
+        Ok(())
+    }
+
+    fn load_matches(&self) -> Result<Vec<MatchRecord>, String> {
+        Ok(self.data()?.matches.clone())
+    }
+
+    fn load_achievements(&self) -> Result<Option<AchievementBook>, String> {
+        Ok(self.data()?.achievements.clone())
+    }
+
+    fn save_achievements(&self, book: &AchievementBook) -> Result<(), String> {
+        self.data()?.achievements = Some(book.clone());
+        Ok(())
+    }
+}
@@ -1,2 +1,12 @@
+pub mod config;
+pub mod envelope;
+pub mod export;
 pub mod file_manager;
+pub mod import;
+pub mod integrity;
 pub mod save_load;
+pub mod storage;
+pub mod json_storage;
+pub mod memory_storage;
+#[cfg(feature = "sqlite")]
+pub mod sqlite_storage;
@@ -1,49 +1,148 @@
+use chrono::{DateTime, Utc};
+
+use crate::achievements::tracker::AchievementBook;
 use crate::game::game_state::GameState;
-use crate::io::file_manager::{read_from_file, write_to_file};
+use crate::game::rules::Ruleset;
+use crate::io::config::RatingSettings;
+use crate::io::storage::Storage;
 use crate::scoreboard::scoreboard::Scoreboard;
 
-const SAVE_STATE_PATH: &str = "saves/save_state.json";
-const SCOREBOARD_PATH: &str = "saves/scoreboard.json";
+/* The slot the single save of older versions shows up as */
+pub const LEGACY_SLOT: &str = "current";
+
+/* What the save list shows about one slot */
+#[derive(Debug, Clone)]
+pub struct SaveSlot {
+    pub name: String,
+    pub player1: String,
+    pub player2: String,
+    pub ruleset: Ruleset,
+    pub score_player1: u32,
+    pub score_player2: u32,
+    pub round: u32,
+    pub saved_at: Option<DateTime<Utc>>,
+    // Changed outside the game since it was saved
+    pub tampered: bool,
+}
+
+impl SaveSlot {
+    fn from_state(name: &str, state: &GameState) -> Self {
+        SaveSlot {
+            name: name.to_string(),
+            player1: state.player1.name.clone(),
+            player2: state.player2.name.clone(),
+            ruleset: state.ruleset.clone(),
+            score_player1: state.score_player1,
+            score_player2: state.score_player2,
+            round: state.current_round,
+            saved_at: state.saved_at,
+            tampered: state.tampered,
+        }
+    }
+}
 
+/* Saves the match into its slot (`state.save_slot`) and the scoreboard next to it */
 pub fn save_game_state_and_scoreboard(
-    state: &GameState,
+    storage: &dyn Storage,
+    state: &mut GameState,
     scoreboard: &Scoreboard,
 ) -> Result<(), String> {
-    let state_json =
-        serde_json::to_string_pretty(state).map_err(|e| format!("Serialize state failed: {e}"))?;
-    let sb_json = serde_json::to_string_pretty(scoreboard)
-        .map_err(|e| format!("Serialize scoreboard failed: {e}"))?;
-
-    write_to_file(SAVE_STATE_PATH, &state_json)
-        .map_err(|e| format!("Write state file failed: {e}"))?;
-    write_to_file(SCOREBOARD_PATH, &sb_json)
-        .map_err(|e| format!("Write scoreboard file failed: {e}"))?;
+    let slot = state
+        .save_slot
+        .clone()
+        .ok_or_else(|| "Save state failed: no save slot chosen".to_string())?;
+    state.saved_at = Some(Utc::now());
 
+    storage.save_game_state(&slot, state)?;
+    storage.save_scoreboard(scoreboard)?;
     Ok(())
 }
 
-pub fn load_game_state_and_scoreboard() -> Result<(GameState, Scoreboard), String> {
-    let state_str =
-        read_from_file(SAVE_STATE_PATH).map_err(|e| format!("Read state failed: {e}"))?;
-    let sb_str =
-        read_from_file(SCOREBOARD_PATH).map_err(|e| format!("Read scoreboard failed: {e}"))?;
+pub fn load_game_state_and_scoreboard(
+    storage: &dyn Storage,
+    slot: &str,
+) -> Result<(GameState, Scoreboard), String> {
+    let mut state = storage
+        .load_game_state(slot)?
+        .ok_or_else(|| format!("Read state failed: no saved game '{slot}'"))?;
+    state.save_slot = Some(slot.to_string());
 
-    let state: GameState =
-        serde_json::from_str(&state_str).map_err(|e| format!("Parse state failed: {e}"))?;
-    let sb: Scoreboard =
-        serde_json::from_str(&sb_str).map_err(|e| format!("Parse scoreboard failed: {e}"))?;
+    let mut sb = storage
+        .load_scoreboard()?
+        .ok_or_else(|| "Read scoreboard failed: no scoreboard".to_string())?;
+    sb.flag_tampered();
+    sb.close_rating_periods(Utc::now());
+
+    // An edited save that was loaded anyway (see save_menu): both players are marked for good
+    if state.tampered {
+        let ids = vec![sb.ensure_player(&state.player1), sb.ensure_player(&state.player2)];
+        sb.flag_edited(ids);
+        storage.save_scoreboard(&sb)?;
+    }
 
     Ok((state, sb))
 }
 
-pub fn load_scoreboard_only() -> Scoreboard {
-    match read_from_file(SCOREBOARD_PATH) {
-        Ok(s) => serde_json::from_str(&s).unwrap_or_else(|_| Scoreboard::new()),
-        Err(_) => Scoreboard::new(),
+/* Every save slot, most recently saved first (unreadable ones are skipped with a warning) */
+pub fn list_save_slots(storage: &dyn Storage) -> Result<Vec<SaveSlot>, String> {
+    let mut slots = Vec::new();
+    for name in storage.list_game_states()? {
+        match storage.load_game_state(&name) {
+            Ok(Some(state)) => slots.push(SaveSlot::from_state(&name, &state)),
+            Ok(None) => {}
+            Err(e) => eprintln!("Warning: save '{name}': {e}"),
+        }
+    }
+
+    slots.sort_by(|a, b| b.saved_at.cmp(&a.saved_at).then_with(|| a.name.cmp(&b.name)));
+    Ok(slots)
+}
+
+pub fn load_scoreboard_only(storage: &dyn Storage) -> Scoreboard {
+    let mut sb = match storage.load_scoreboard() {
+        Ok(sb) => sb.unwrap_or_else(Scoreboard::new),
+        Err(e) => {
+            eprintln!("Warning: {e} - no usable backup, starting with an empty scoreboard");
+            Scoreboard::new()
+        }
+    };
+    sb.flag_tampered();
+    // Rating periods that ended since the last match: idle players' deviations grow
+    sb.close_rating_periods(Utc::now());
+    sb
+}
+
+/* Writes the configured rating settings into the scoreboard, which keeps them (replays use them too) */
+pub fn apply_rating_settings(storage: &dyn Storage, settings: &RatingSettings) -> Result<(), String> {
+    if settings.elo_k_factor.is_none() && settings.period_days.is_none() {
+        return Ok(());
     }
+
+    let mut sb = storage.load_scoreboard()?.unwrap_or_else(Scoreboard::new);
+    // Saving now would re-sign an edited scoreboard before the main menu reports it
+    if sb.tampering.is_some() {
+        return Ok(());
+    }
+    let now = Utc::now();
+    sb.close_rating_periods(now);
+    if sb.apply_rating_settings(settings.elo_k_factor, settings.period_days, now) {
+        sb.save(storage)?;
+    }
+    Ok(())
 }
 
+pub fn load_achievements_only(storage: &dyn Storage, scoreboard: &Scoreboard) -> AchievementBook {
+    let mut book = match storage.load_achievements() {
+        Ok(book) => book.unwrap_or_default(),
+        Err(e) => {
+            eprintln!("Warning: {e} - no usable backup, starting without achievements");
+            AchievementBook::default()
+        }
+    };
+    book.migrate_legacy_names(scoreboard);
+    book
+}
 
-pub fn delete_save_state() {
-    let _ = std::fs::remove_file("saves/save_state.json");
+pub fn delete_save_state(storage: &dyn Storage, slot: &str) -> Result<(), String> {
+    storage.delete_game_state(slot)
 }
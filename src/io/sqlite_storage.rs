@@ -0,0 +1,726 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension, Row};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::achievements::tracker::{AchievementBook, Unlock};
+use crate::game::game_state::GameState;
+use crate::game::round::RoundRecord;
+use crate::history::match_record::MatchRecord;
+use crate::history::query::MatchQuery;
+use crate::io::envelope::{from_json, to_json, FileKind};
+use crate::io::storage::Storage;
+use crate::players::profile::PlayerProfile;
+use crate::scoreboard::glicko::GlickoRating;
+use crate::scoreboard::scoreboard::Scoreboard;
+use crate::scoreboard::stats::PlayerStats;
+
+/* Schema Migrations - `PRAGMA user_version` counts how many have been applied
+ * Never edit one that has shipped; add the change as a new entry at the end.
+*/
+const MIGRATIONS: &[&str] = &[
+    // v1: initial schema
+    "
+    CREATE TABLE players (
+        id                      TEXT PRIMARY KEY,
+        display_name            TEXT NOT NULL,
+        aliases                 TEXT NOT NULL DEFAULT '[]',
+        last_played             TEXT,
+        preferences             TEXT NOT NULL DEFAULT '{}',
+        matches_played          INTEGER NOT NULL DEFAULT 0,
+        matches_won             INTEGER NOT NULL DEFAULT 0,
+        rounds_won              INTEGER NOT NULL DEFAULT 0,
+        reaction_time_total_ms  INTEGER NOT NULL DEFAULT 0,
+        timed_moves             INTEGER NOT NULL DEFAULT 0,
+        opponents               TEXT NOT NULL DEFAULT '{}',
+        tendencies              TEXT NOT NULL DEFAULT '{}',
+        streaks                 TEXT NOT NULL DEFAULT '{}'
+    );
+
+    -- Elo is kept per ruleset, Glicko-2 once per player (ruleset = '')
+    CREATE TABLE ratings (
+        player_id   TEXT NOT NULL REFERENCES players(id) ON DELETE CASCADE,
+        system      TEXT NOT NULL,
+        ruleset     TEXT NOT NULL DEFAULT '',
+        rating      REAL NOT NULL,
+        deviation   REAL,
+        volatility  REAL,
+        PRIMARY KEY (player_id, system, ruleset)
+    );
+
+    CREATE TABLE matches (
+        id              TEXT PRIMARY KEY,
+        played_at       TEXT NOT NULL,
+        player1_id      TEXT,
+        player1         TEXT NOT NULL,
+        player2_id      TEXT,
+        player2         TEXT NOT NULL,
+        ruleset         TEXT NOT NULL,
+        match_format    TEXT NOT NULL,
+        turn_timer      TEXT,
+        blitz           TEXT,
+        score_player1   INTEGER NOT NULL,
+        score_player2   INTEGER NOT NULL,
+        outcome         TEXT NOT NULL
+    );
+    CREATE INDEX matches_played_at ON matches(played_at);
+    CREATE INDEX matches_player1 ON matches(player1_id);
+    CREATE INDEX matches_player2 ON matches(player2_id);
+
+    CREATE TABLE rounds (
+        match_id        TEXT NOT NULL REFERENCES matches(id) ON DELETE CASCADE,
+        round_no        INTEGER NOT NULL,
+        gesture_p1      TEXT,
+        gesture_p2      TEXT,
+        result          TEXT NOT NULL,
+        reaction_ms_p1  INTEGER,
+        reaction_ms_p2  INTEGER,
+        won_on_speed    INTEGER NOT NULL DEFAULT 0,
+        bonus_point     INTEGER NOT NULL DEFAULT 0,
+        PRIMARY KEY (match_id, round_no)
+    );
+
+    CREATE TABLE saves (
+        name      TEXT PRIMARY KEY,
+        saved_at  TEXT NOT NULL,
+        state     TEXT NOT NULL
+    );
+
+    CREATE TABLE achievements (
+        player_id    TEXT NOT NULL,
+        achievement  TEXT NOT NULL,
+        unlocked_at  TEXT NOT NULL,
+        match_id     TEXT NOT NULL,
+        PRIMARY KEY (player_id, achievement)
+    );
+
+    -- Scoreboard-wide settings (Elo K-factor, rating periods, all-time records)
+    CREATE TABLE meta (
+        key    TEXT PRIMARY KEY,
+        value  TEXT NOT NULL
+    );
+    ",
+    // v2: players flagged by tamper detection stay flagged
+    "
+    ALTER TABLE players ADD COLUMN tampered INTEGER NOT NULL DEFAULT 0;
+    ",
+];
+
+const MATCH_COLUMNS: &str = "id, played_at, player1, player2, ruleset, match_format, turn_timer, \
+                             blitz, score_player1, score_player2, outcome";
+
+/* Embedded SQLite Database (one file, every table) */
+pub struct SqliteStorage {
+    conn: Mutex<Connection>,
+}
+
+impl SqliteStorage {
+    /* Opens (or creates) the database and brings the schema up to date
+     * A brand new database starts with whatever `legacy` (the JSON saves) already holds.
+    */
+    pub fn open(path: &Path, legacy: &dyn Storage) -> Result<Self, String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Create database dir failed: {e}"))?;
+        }
+
+        let mut conn = Connection::open(path).map_err(|e| format!("Open database failed: {e}"))?;
+        conn.pragma_update(None, "foreign_keys", true).map_err(db_err)?;
+        let previous_version = migrate(&mut conn)?;
+
+        let db = Self {
+            conn: Mutex::new(conn),
+        };
+        if previous_version == 0 {
+            db.import_from(legacy)?;
+        }
+        Ok(db)
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, Connection>, String> {
+        self.conn.lock().map_err(|_| "Database lock poisoned".to_string())
+    }
+
+    /* One-off copy of everything `other` holds (used when the database is created) */
+    fn import_from(&self, other: &dyn Storage) -> Result<(), String> {
+        let mut conn = self.lock()?;
+        let tx = conn.transaction().map_err(db_err)?;
+
+        let scoreboard = other.load_scoreboard()?.map(|mut sb| {
+            // The database has no signatures - edited players have to carry the flag over
+            sb.flag_tampered();
+            sb
+        });
+        if let Some(sb) = &scoreboard {
+            write_scoreboard(&tx, sb)?;
+        }
+        if let Some(mut book) = other.load_achievements()? {
+            if let Some(sb) = &scoreboard {
+                book.migrate_legacy_names(sb);
+            }
+            write_achievements(&tx, &book)?;
+        }
+        for name in other.list_game_states()? {
+            if let Some(state) = other.load_game_state(&name)? {
+                write_game_state(&tx, &name, &state)?;
+            }
+        }
+        for record in other.load_matches()? {
+            insert_match(&tx, &record)?;
+        }
+
+        tx.commit().map_err(db_err)
+    }
+}
+
+/* Applies the migrations not run yet >> schema version before */
+fn migrate(conn: &mut Connection) -> Result<usize, String> {
+    let version: i64 = conn
+        .pragma_query_value(None, "user_version", |row| row.get(0))
+        .map_err(db_err)?;
+    let version = version as usize;
+
+    if version > MIGRATIONS.len() {
+        return Err(format!(
+            "Database schema v{version} is newer than this build understands (v{})",
+            MIGRATIONS.len()
+        ));
+    }
+
+    for (index, sql) in MIGRATIONS.iter().enumerate().skip(version) {
+        let tx = conn.transaction().map_err(db_err)?;
+        tx.execute_batch(sql)
+            .map_err(|e| format!("Database migration to v{} failed: {e}", index + 1))?;
+        tx.pragma_update(None, "user_version", (index + 1) as i64).map_err(db_err)?;
+        tx.commit().map_err(db_err)?;
+    }
+
+    Ok(version)
+}
+
+fn db_err(e: rusqlite::Error) -> String {
+    format!("Database query failed: {e}")
+}
+
+/* Column text for a value: plain for simple enums ("Classic"), JSON for anything else */
+fn to_text<T: Serialize>(value: &T) -> Result<String, String> {
+    match serde_json::to_value(value).map_err(|e| format!("Serialize column failed: {e}"))? {
+        Value::String(s) => Ok(s),
+        other => Ok(other.to_string()),
+    }
+}
+
+fn from_text<T: DeserializeOwned>(text: &str) -> Result<T, String> {
+    serde_json::from_value(Value::String(text.to_string()))
+        .or_else(|_| serde_json::from_str(text))
+        .map_err(|e| format!("Parse column failed: {e}"))
+}
+
+// Fixed-width UTC timestamps, so comparing the text compares the time
+fn timestamp(at: &DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+fn parse_timestamp(text: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(text)
+        .map(|at| at.with_timezone(&Utc))
+        .map_err(|e| format!("Parse timestamp failed: {e}"))
+}
+
+fn get<T: rusqlite::types::FromSql>(row: &Row, index: usize) -> Result<T, String> {
+    row.get(index).map_err(db_err)
+}
+
+// A nullable to_text column
+fn get_optional<T: DeserializeOwned>(row: &Row, index: usize) -> Result<Option<T>, String> {
+    get::<Option<String>>(row, index)?.as_deref().map(from_text).transpose()
+}
+
+/* Scoreboard */
+
+fn write_scoreboard(conn: &Connection, sb: &Scoreboard) -> Result<(), String> {
+    let mut upsert = conn
+        .prepare(
+            "INSERT INTO players (id, display_name, aliases, last_played, preferences, matches_played,
+                 matches_won, rounds_won, reaction_time_total_ms, timed_moves, opponents, tendencies, streaks,
+                 tampered)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)
+             ON CONFLICT(id) DO UPDATE SET
+                 display_name = excluded.display_name, aliases = excluded.aliases,
+                 last_played = excluded.last_played, preferences = excluded.preferences,
+                 matches_played = excluded.matches_played, matches_won = excluded.matches_won,
+                 rounds_won = excluded.rounds_won, reaction_time_total_ms = excluded.reaction_time_total_ms,
+                 timed_moves = excluded.timed_moves, opponents = excluded.opponents,
+                 tendencies = excluded.tendencies, streaks = excluded.streaks,
+                 tampered = excluded.tampered",
+        )
+        .map_err(db_err)?;
+    let mut insert_rating = conn
+        .prepare(
+            "INSERT INTO ratings (player_id, system, ruleset, rating, deviation, volatility)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )
+        .map_err(db_err)?;
+
+    // Players merged away since the last save
+    let stored: Vec<String> = conn
+        .prepare("SELECT id FROM players")
+        .and_then(|mut stmt| stmt.query_map([], |row| row.get(0))?.collect())
+        .map_err(db_err)?;
+    for id in stored.iter().filter(|id| !sb.players.contains_key(*id)) {
+        conn.execute("DELETE FROM players WHERE id = ?1", [id]).map_err(db_err)?;
+    }
+
+    conn.execute("DELETE FROM ratings", []).map_err(db_err)?;
+
+    for (id, stats) in &sb.players {
+        let profile = sb.profiles.get(id);
+        upsert
+            .execute(params![
+                id,
+                profile.map_or(id.as_str(), |p| p.display_name.as_str()),
+                to_text(&profile.map(|p| p.aliases.clone()).unwrap_or_default())?,
+                profile.and_then(|p| p.last_played).map(|at| timestamp(&at)),
+                to_text(&profile.map(|p| p.preferences.clone()).unwrap_or_default())?,
+                stats.matches_played,
+                stats.matches_won,
+                stats.rounds_won,
+                stats.reaction_time_total_ms as i64,
+                stats.timed_moves,
+                to_text(&stats.opponents)?,
+                to_text(&stats.tendencies)?,
+                to_text(&stats.streaks)?,
+                stats.tampered,
+            ])
+            .map_err(db_err)?;
+
+        for (ruleset, rating) in &stats.ratings {
+            insert_rating
+                .execute(params![id, "elo", to_text(ruleset)?, rating, None::<f64>, None::<f64>])
+                .map_err(db_err)?;
+        }
+        let glicko = stats.glicko;
+        insert_rating
+            .execute(params![id, "glicko2", "", glicko.rating, glicko.deviation, glicko.volatility])
+            .map_err(db_err)?;
+    }
+
+    for (key, value) in [
+        ("elo_k_factor", to_text(&sb.elo_k_factor)?),
+        ("rating_periods", to_text(&sb.rating_periods)?),
+        ("records", to_text(&sb.records)?),
+    ] {
+        conn.execute(
+            "INSERT OR REPLACE INTO meta (key, value) VALUES (?1, ?2)",
+            params![key, value],
+        )
+        .map_err(db_err)?;
+    }
+
+    Ok(())
+}
+
+fn read_scoreboard(conn: &Connection) -> Result<Option<Scoreboard>, String> {
+    let meta: HashMap<String, String> = conn
+        .prepare("SELECT key, value FROM meta")
+        .and_then(|mut stmt| stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?.collect())
+        .map_err(db_err)?;
+    let player_count: i64 = conn
+        .query_row("SELECT COUNT(*) FROM players", [], |row| row.get(0))
+        .map_err(db_err)?;
+    if meta.is_empty() && player_count == 0 {
+        return Ok(None);
+    }
+
+    let mut sb = Scoreboard::new();
+    if let Some(v) = meta.get("elo_k_factor") {
+        sb.elo_k_factor = from_text(v)?;
+    }
+    if let Some(v) = meta.get("rating_periods") {
+        sb.rating_periods = from_text(v)?;
+    }
+    if let Some(v) = meta.get("records") {
+        sb.records = from_text(v)?;
+    }
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, display_name, aliases, last_played, preferences, matches_played, matches_won,
+                 rounds_won, reaction_time_total_ms, timed_moves, opponents, tendencies, streaks, tampered
+             FROM players",
+        )
+        .map_err(db_err)?;
+    let mut rows = stmt.query([]).map_err(db_err)?;
+    while let Some(row) = rows.next().map_err(db_err)? {
+        let id: String = get(row, 0)?;
+        let last_played: Option<String> = get(row, 3)?;
+
+        let profile = PlayerProfile {
+            id: id.clone(),
+            display_name: get(row, 1)?,
+            aliases: from_text(&get::<String>(row, 2)?)?,
+            last_played: last_played.as_deref().map(parse_timestamp).transpose()?,
+            preferences: from_text(&get::<String>(row, 4)?)?,
+        };
+
+        let mut stats = PlayerStats::new();
+        stats.matches_played = get(row, 5)?;
+        stats.matches_won = get(row, 6)?;
+        stats.rounds_won = get(row, 7)?;
+        stats.reaction_time_total_ms = get::<i64>(row, 8)? as u64;
+        stats.timed_moves = get(row, 9)?;
+        stats.opponents = from_text(&get::<String>(row, 10)?)?;
+        stats.tendencies = from_text(&get::<String>(row, 11)?)?;
+        stats.streaks = from_text(&get::<String>(row, 12)?)?;
+        stats.tampered = get(row, 13)?;
+
+        sb.profiles.insert(id.clone(), profile);
+        sb.players.insert(id, stats);
+    }
+
+    let mut stmt = conn
+        .prepare("SELECT player_id, system, ruleset, rating, deviation, volatility FROM ratings")
+        .map_err(db_err)?;
+    let mut rows = stmt.query([]).map_err(db_err)?;
+    while let Some(row) = rows.next().map_err(db_err)? {
+        let id: String = get(row, 0)?;
+        let Some(stats) = sb.players.get_mut(&id) else {
+            continue;
+        };
+
+        let rating: f64 = get(row, 3)?;
+        match get::<String>(row, 1)?.as_str() {
+            "elo" => stats.set_rating(&from_text(&get::<String>(row, 2)?)?, rating),
+            "glicko2" => {
+                stats.glicko = GlickoRating {
+                    rating,
+                    deviation: get::<Option<f64>>(row, 4)?.unwrap_or(stats.glicko.deviation),
+                    volatility: get::<Option<f64>>(row, 5)?.unwrap_or(stats.glicko.volatility),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(Some(sb))
+}
+
+/* Matches */
+
+fn insert_match(conn: &Connection, record: &MatchRecord) -> Result<(), String> {
+    conn.execute(
+        "INSERT INTO matches (id, played_at, player1_id, player1, player2_id, player2, ruleset,
+             match_format, turn_timer, blitz, score_player1, score_player2, outcome)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+        params![
+            record.id,
+            timestamp(&record.played_at),
+            record.player1.profile_id,
+            to_text(&record.player1)?,
+            record.player2.profile_id,
+            to_text(&record.player2)?,
+            to_text(&record.ruleset)?,
+            to_text(&record.match_format)?,
+            record.turn_timer.as_ref().map(to_text).transpose()?,
+            record.blitz.as_ref().map(to_text).transpose()?,
+            record.score_player1,
+            record.score_player2,
+            to_text(&record.outcome)?,
+        ],
+    )
+    .map_err(db_err)?;
+
+    let mut insert_round = conn
+        .prepare(
+            "INSERT INTO rounds (match_id, round_no, gesture_p1, gesture_p2, result,
+                 reaction_ms_p1, reaction_ms_p2, won_on_speed, bonus_point)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        )
+        .map_err(db_err)?;
+    for (index, round) in record.rounds.iter().enumerate() {
+        insert_round
+            .execute(params![
+                record.id,
+                index as i64 + 1,
+                round.gesture_p1.as_ref().map(to_text).transpose()?,
+                round.gesture_p2.as_ref().map(to_text).transpose()?,
+                to_text(&round.result)?,
+                round.reaction_ms_p1.map(|ms| ms as i64),
+                round.reaction_ms_p2.map(|ms| ms as i64),
+                round.won_on_speed,
+                round.bonus_point,
+            ])
+            .map_err(db_err)?;
+    }
+
+    Ok(())
+}
+
+/* Matches passing `query`, oldest first, with their rounds */
+fn select_matches(conn: &Connection, query: &MatchQuery) -> Result<Vec<MatchRecord>, String> {
+    let mut clauses: Vec<String> = Vec::new();
+    let mut values: Vec<String> = Vec::new();
+
+    if let Some(ruleset) = &query.ruleset {
+        values.push(to_text(ruleset)?);
+        clauses.push(format!("ruleset = ?{}", values.len()));
+    }
+    if let Some(from) = &query.from {
+        values.push(timestamp(from));
+        clauses.push(format!("played_at >= ?{}", values.len()));
+    }
+    if let Some(until) = &query.until {
+        values.push(timestamp(until));
+        clauses.push(format!("played_at < ?{}", values.len()));
+    }
+    let filter = if clauses.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", clauses.join(" AND "))
+    };
+
+    /* Rounds of every selected match in one go */
+    let mut rounds: HashMap<String, Vec<RoundRecord>> = HashMap::new();
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT match_id, gesture_p1, gesture_p2, result, reaction_ms_p1, reaction_ms_p2,
+                 won_on_speed, bonus_point
+             FROM rounds WHERE match_id IN (SELECT id FROM matches {filter})
+             ORDER BY match_id, round_no"
+        ))
+        .map_err(db_err)?;
+    let mut rows = stmt.query(params_from_iter(values.iter())).map_err(db_err)?;
+    while let Some(row) = rows.next().map_err(db_err)? {
+        let round = RoundRecord {
+            gesture_p1: get_optional(row, 1)?,
+            gesture_p2: get_optional(row, 2)?,
+            result: from_text(&get::<String>(row, 3)?)?,
+            reaction_ms_p1: get::<Option<i64>>(row, 4)?.map(|ms| ms as u64),
+            reaction_ms_p2: get::<Option<i64>>(row, 5)?.map(|ms| ms as u64),
+            won_on_speed: get(row, 6)?,
+            bonus_point: get(row, 7)?,
+        };
+        rounds.entry(get(row, 0)?).or_default().push(round);
+    }
+
+    let mut records = Vec::new();
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {MATCH_COLUMNS} FROM matches {filter} ORDER BY played_at, rowid"
+        ))
+        .map_err(db_err)?;
+    let mut rows = stmt.query(params_from_iter(values.iter())).map_err(db_err)?;
+    while let Some(row) = rows.next().map_err(db_err)? {
+        let id: String = get(row, 0)?;
+        records.push(MatchRecord {
+            rounds: rounds.remove(&id).unwrap_or_default(),
+            id,
+            played_at: parse_timestamp(&get::<String>(row, 1)?)?,
+            player1: from_text(&get::<String>(row, 2)?)?,
+            player2: from_text(&get::<String>(row, 3)?)?,
+            ruleset: from_text(&get::<String>(row, 4)?)?,
+            match_format: from_text(&get::<String>(row, 5)?)?,
+            turn_timer: get_optional(row, 6)?,
+            blitz: get_optional(row, 7)?,
+            score_player1: get(row, 8)?,
+            score_player2: get(row, 9)?,
+            outcome: from_text(&get::<String>(row, 10)?)?,
+        });
+    }
+
+    // The player filter runs here: older matches have no player IDs and are matched by name
+    records.retain(|record| query.matches(record));
+    Ok(records)
+}
+
+/* Saved Game */
+
+fn write_game_state(conn: &Connection, name: &str, state: &GameState) -> Result<(), String> {
+    let json = to_json(FileKind::GameState, state, false).map_err(|e| format!("Serialize state failed: {e}"))?;
+    conn.execute(
+        "INSERT OR REPLACE INTO saves (name, saved_at, state) VALUES (?1, ?2, ?3)",
+        params![name, timestamp(&Utc::now()), json],
+    )
+    .map_err(db_err)?;
+    Ok(())
+}
+
+/* Achievements */
+
+fn write_achievements(conn: &Connection, book: &AchievementBook) -> Result<(), String> {
+    conn.execute("DELETE FROM achievements", []).map_err(db_err)?;
+
+    let mut insert = conn
+        .prepare(
+            "INSERT OR REPLACE INTO achievements (player_id, achievement, unlocked_at, match_id)
+             VALUES (?1, ?2, ?3, ?4)",
+        )
+        .map_err(db_err)?;
+    for (player_id, unlocks) in &book.players {
+        for unlock in unlocks {
+            insert
+                .execute(params![
+                    player_id,
+                    to_text(&unlock.achievement)?,
+                    timestamp(&unlock.unlocked_at),
+                    unlock.match_id,
+                ])
+                .map_err(db_err)?;
+        }
+    }
+
+    Ok(())
+}
+
+impl Storage for SqliteStorage {
+    fn load_scoreboard(&self) -> Result<Option<Scoreboard>, String> {
+        let conn = self.lock()?;
+        read_scoreboard(&conn)
+    }
+
+    fn save_scoreboard(&self, scoreboard: &Scoreboard) -> Result<(), String> {
+        let mut conn = self.lock()?;
+        let tx = conn.transaction().map_err(db_err)?;
+        write_scoreboard(&tx, scoreboard)?;
+        tx.commit().map_err(db_err)
+    }
+
+    fn list_game_states(&self) -> Result<Vec<String>, String> {
+        let conn = self.lock()?;
+        let mut select = conn.prepare("SELECT name FROM saves ORDER BY name").map_err(db_err)?;
+        let names = select
+            .query_map([], |row| row.get(0))
+            .map_err(db_err)?
+            .collect::<Result<Vec<String>, _>>()
+            .map_err(db_err)?;
+        Ok(names)
+    }
+
+    fn load_game_state(&self, name: &str) -> Result<Option<GameState>, String> {
+        let json: Option<String> = self
+            .lock()?
+            .query_row("SELECT state FROM saves WHERE name = ?1", [name], |row| row.get(0))
+            .optional()
+            .map_err(db_err)?;
+
+        json.map(|s| from_json(FileKind::GameState, &s).map_err(|e| format!("Parse state failed: {e}")))
+            .transpose()
+    }
+
+    fn save_game_state(&self, name: &str, state: &GameState) -> Result<(), String> {
+        let conn = self.lock()?;
+        write_game_state(&conn, name, state)
+    }
+
+    fn delete_game_state(&self, name: &str) -> Result<(), String> {
+        self.lock()?
+            .execute("DELETE FROM saves WHERE name = ?1", [name])
+            .map_err(db_err)?;
+        Ok(())
+    }
+
+    fn append_match(&self, record: &MatchRecord) -> Result<(), String> {
+        let mut conn = self.lock()?;
+        let tx = conn.transaction().map_err(db_err)?;
+        insert_match(&tx, record)?;
+        tx.commit().map_err(db_err)
+    }
+
+    fn load_matches(&self) -> Result<Vec<MatchRecord>, String> {
+        let conn = self.lock()?;
+        select_matches(&conn, &MatchQuery::default())
+    }
+
+    fn find_matches(&self, query: &MatchQuery) -> Result<Vec<MatchRecord>, String> {
+        let conn = self.lock()?;
+        select_matches(&conn, query)
+    }
+
+    fn load_achievements(&self) -> Result<Option<AchievementBook>, String> {
+        let conn = self.lock()?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT player_id, achievement, unlocked_at, match_id FROM achievements
+                 ORDER BY unlocked_at",
+            )
+            .map_err(db_err)?;
+        let mut rows = stmt.query([]).map_err(db_err)?;
+
+        let mut book = AchievementBook::default();
+        while let Some(row) = rows.next().map_err(db_err)? {
+            let unlock = Unlock {
+                achievement: from_text(&get::<String>(row, 1)?)?,
+                unlocked_at: parse_timestamp(&get::<String>(row, 2)?)?,
+                match_id: get(row, 3)?,
+            };
+            book.players.entry(get(row, 0)?).or_default().push(unlock);
+        }
+
+        Ok((!book.players.is_empty()).then_some(book))
+    }
+
+    fn save_achievements(&self, book: &AchievementBook) -> Result<(), String> {
+        let mut conn = self.lock()?;
+        let tx = conn.transaction().map_err(db_err)?;
+        write_achievements(&tx, book)?;
+        tx.commit().map_err(db_err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::memory_storage::MemoryStorage;
+    use crate::players::player::Player;
+    use crate::utils::id::new_id;
+
+    // A fresh database file, removed again when the test is done
+    struct TempDb(std::path::PathBuf);
+
+    impl TempDb {
+        fn new() -> Self {
+            TempDb(std::env::temp_dir().join(format!("pl-project-test-{}.db", new_id())))
+        }
+    }
+
+    impl Drop for TempDb {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn tampered_flag_survives_a_round_trip() {
+        let db = TempDb::new();
+        let storage = SqliteStorage::open(&db.0, &MemoryStorage::default()).unwrap();
+
+        let mut sb = Scoreboard::new();
+        let edited = sb.ensure_player(&Player::new_human("Ana"));
+        let clean = sb.ensure_player(&Player::new_human("Ben"));
+        sb.players.get_mut(&edited).unwrap().tampered = true;
+        storage.save_scoreboard(&sb).unwrap();
+
+        let loaded = storage.load_scoreboard().unwrap().unwrap();
+        assert!(loaded.players[&edited].tampered);
+        assert!(!loaded.players[&clean].tampered);
+    }
+
+    #[test]
+    fn tampered_flag_is_kept_when_importing_json_saves() {
+        let legacy = MemoryStorage::default();
+        let mut sb = Scoreboard::new();
+        let edited = sb.ensure_player(&Player::new_human("Ana"));
+        // As a JSON load reports it: found on load, not flagged in the stats yet
+        sb.tampering = Some(vec![edited.clone()]);
+        legacy.save_scoreboard(&sb).unwrap();
+
+        let db = TempDb::new();
+        let storage = SqliteStorage::open(&db.0, &legacy).unwrap();
+        let loaded = storage.load_scoreboard().unwrap().unwrap();
+        assert!(loaded.players[&edited].tampered);
+    }
+}
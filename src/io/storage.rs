@@ -0,0 +1,191 @@
+use std::path::{Path, PathBuf};
+
+use crate::achievements::tracker::AchievementBook;
+use crate::game::game_state::GameState;
+use crate::history::match_record::MatchRecord;
+use crate::history::query::MatchQuery;
+use crate::io::config::Config;
+use crate::io::json_storage::JsonStorage;
+use crate::io::memory_storage::MemoryStorage;
+use crate::scoreboard::scoreboard::Scoreboard;
+
+/* Where everything the game remembers is kept
+ * Opened once at startup and handed to whatever needs it - nothing else touches the disk.
+ * `load_*` return Ok(None) when nothing was saved yet, Err when it's there but unreadable.
+*/
+pub trait Storage: Send + Sync {
+    fn load_scoreboard(&self) -> Result<Option<Scoreboard>, String>;
+    fn save_scoreboard(&self, scoreboard: &Scoreboard) -> Result<(), String>;
+
+    /* Puts back the newest backup that wasn't changed outside the game (None if there is none) */
+    fn restore_scoreboard_backup(&self) -> Result<Option<Scoreboard>, String> {
+        Ok(None)
+    }
+
+    /* Unfinished matches, by name */
+    fn list_game_states(&self) -> Result<Vec<String>, String>;
+    fn load_game_state(&self, name: &str) -> Result<Option<GameState>, String>;
+    fn save_game_state(&self, name: &str, state: &GameState) -> Result<(), String>;
+    fn delete_game_state(&self, name: &str) -> Result<(), String>;
+
+    /* Same as restore_scoreboard_backup, for one save */
+    fn restore_game_state_backup(&self, _name: &str) -> Result<Option<GameState>, String> {
+        Ok(None)
+    }
+
+    /* Finished matches (oldest first) */
+    fn append_match(&self, record: &MatchRecord) -> Result<(), String>;
+    fn load_matches(&self) -> Result<Vec<MatchRecord>, String>;
+
+    /* Log entries changed outside the game - load_matches leaves them out until they're settled
+     * by removing them for good or accepting them as they are now.
+     */
+    fn load_tampered_matches(&self) -> Result<Vec<MatchRecord>, String> {
+        Ok(Vec::new())
+    }
+    fn remove_tampered_matches(&self) -> Result<(), String> {
+        Ok(())
+    }
+    fn accept_tampered_matches(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    // Backends that can filter on their side override this
+    fn find_matches(&self, query: &MatchQuery) -> Result<Vec<MatchRecord>, String> {
+        Ok(self
+            .load_matches()?
+            .into_iter()
+            .filter(|r| query.matches(r))
+            .collect())
+    }
+
+    fn load_achievements(&self) -> Result<Option<AchievementBook>, String>;
+    fn save_achievements(&self, book: &AchievementBook) -> Result<(), String>;
+
+    /* Same as restore_scoreboard_backup, for the achievements */
+    fn restore_achievements_backup(&self) -> Result<Option<AchievementBook>, String> {
+        Ok(None)
+    }
+}
+
+/* Save names end up in file names, so keep them to letters, digits, spaces, '-' and '_' */
+pub fn check_save_name(name: &str) -> Result<(), String> {
+    let allowed = |c: char| c.is_ascii_alphanumeric() || matches!(c, ' ' | '-' | '_');
+    if name.trim().is_empty() || name.len() > 40 || !name.chars().all(allowed) {
+        return Err(format!("Invalid save name '{name}'"));
+    }
+    Ok(())
+}
+
+const APP_DIR: &str = "pl-project";
+
+// Where saves lived before they had a fixed home (relative to wherever the game was started)
+const LEGACY_SAVES_DIR: &str = "saves";
+const LEGACY_FILES: [&str; 5] = [
+    "scoreboard.json",
+    "save_state.json",
+    "match_log.jsonl",
+    "achievements.json",
+    "pl-project.db",
+];
+
+/* Data Directory (the same no matter where the game is started from)
+ * `configured` (PL_DATA_DIR or `data_dir`, checked by the config), else $XDG_DATA_HOME/pl-project, else
+ * ~/.local/share/pl-project (%APPDATA%\pl-project on Windows), else a `saves` folder next to the executable.
+*/
+pub fn data_dir(configured: Option<&Path>) -> PathBuf {
+    let env_dir = |name: &str| {
+        std::env::var_os(name)
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+    };
+
+    if let Some(dir) = configured {
+        return dir.to_path_buf();
+    }
+    if let Some(xdg) = env_dir("XDG_DATA_HOME").filter(|d| d.is_absolute()) {
+        return xdg.join(APP_DIR);
+    }
+    if cfg!(windows)
+        && let Some(appdata) = env_dir("APPDATA")
+    {
+        return appdata.join(APP_DIR);
+    }
+    if let Some(home) = env_dir("HOME") {
+        return home.join(".local").join("share").join(APP_DIR);
+    }
+
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(LEGACY_SAVES_DIR)))
+        .unwrap_or_else(|| PathBuf::from(LEGACY_SAVES_DIR))
+}
+
+/* Picks the backend from PL_STORAGE: json (default), sqlite (needs the `sqlite` feature) or memory */
+pub fn open_storage(config: &Config) -> Box<dyn Storage> {
+    let dir = data_dir(config.data_dir.as_deref());
+    let kind = std::env::var("PL_STORAGE").unwrap_or_default().to_lowercase();
+
+    if kind != "memory" {
+        adopt_legacy_saves(&dir);
+    }
+
+    match kind.as_str() {
+        "" | "json" => Box::new(JsonStorage::new(dir)),
+        "memory" => Box::new(MemoryStorage::default()),
+        "sqlite" => open_sqlite(&dir),
+        other => {
+            eprintln!("Warning: unknown PL_STORAGE '{other}', using JSON files");
+            Box::new(JsonStorage::new(dir))
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+fn open_sqlite(dir: &Path) -> Box<dyn Storage> {
+    use crate::io::sqlite_storage::SqliteStorage;
+
+    let json = JsonStorage::new(dir);
+    match SqliteStorage::open(&dir.join("pl-project.db"), &json) {
+        Ok(db) => Box::new(db),
+        Err(e) => {
+            eprintln!("Warning: {e} - using JSON files");
+            Box::new(json)
+        }
+    }
+}
+
+#[cfg(not(feature = "sqlite"))]
+fn open_sqlite(dir: &Path) -> Box<dyn Storage> {
+    eprintln!("Warning: built without the `sqlite` feature - using JSON files");
+    Box::new(JsonStorage::new(dir))
+}
+
+/* First start with a fixed data directory: copy over a `saves/` folder from the current directory
+ * Copied, not moved - the old folder stays untouched in case something goes wrong.
+*/
+fn adopt_legacy_saves(dir: &Path) {
+    let legacy = Path::new(LEGACY_SAVES_DIR);
+    let already_there = LEGACY_FILES.iter().any(|f| dir.join(f).exists());
+    let same_dir = match (legacy.canonicalize(), dir.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    };
+    if already_there || same_dir || !legacy.join("scoreboard.json").exists() {
+        return;
+    }
+
+    if let Err(e) = std::fs::create_dir_all(dir) {
+        eprintln!("Warning: could not create {}: {e}", dir.display());
+        return;
+    }
+    for file in LEGACY_FILES {
+        let from = legacy.join(file);
+        if from.exists()
+            && let Err(e) = std::fs::copy(&from, dir.join(file))
+        {
+            eprintln!("Warning: could not copy {}: {e}", from.display());
+        }
+    }
+    eprintln!("Copied saves from ./{LEGACY_SAVES_DIR} to {}", dir.display());
+}
@@ -1,15 +1,38 @@
+mod achievements;
+mod cli;
 mod game;
+mod history;
 mod players;
+mod report;
 mod display;
 mod io;
 mod scoreboard;
 mod menu;
 mod utils;
 
-use menu::main_menu::run_main_menu;
+use io::config::load_config;
+use io::save_load::apply_rating_settings;
+use io::storage::open_storage;
 
 fn main() {
-    let _ = std::fs::create_dir_all("saves");   
-    run_main_menu();
-}
+    let config = match load_config() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(2);
+        }
+    };
+    config.apply_color_mode();
+    let storage = open_storage(&config);
+    if let Err(e) = apply_rating_settings(storage.as_ref(), &config.ratings) {
+        eprintln!("Warning: {e} - rating settings from the config not applied");
+    }
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
 
+    let code = cli::run(&args, storage.as_ref(), &config);
+    if code != 0 {
+        std::process::exit(code);
+    }
+}
@@ -1,40 +1,55 @@
-use crate::display::colors::ColorSupport;
+use crate::achievements::tracker::AchievementBook;
+use crate::display::colors::{failure, success, ColorSupport};
 use crate::display::ui::{
-    ask_main_menu_choice, show_main_menu, show_scoreboard, show_scoreboard_sort_menu,
+    ask_main_menu_choice, marked, show_all_time_records, show_glicko_leaderboard, show_main_menu,
+    show_rating_leaderboard, show_scoreboard, show_scoreboard_sort_menu, show_streak_leaders,
+    show_tamper_warning,
 };
 use crate::game::game_loop::run_match;
-use crate::game::game_state::GameState;
+use crate::game::rules::Ruleset;
+use crate::history::query::MatchQuery;
+use crate::io::config::Config;
 use crate::io::save_load::load_game_state_and_scoreboard;
+use crate::io::storage::Storage;
+use crate::menu::profile_menu::{run_profiles_menu, theme_for};
+use crate::menu::replay_menu::run_replays_menu;
+use crate::menu::save_menu::pick_save_slot;
 use crate::menu::settings_menu::setup_new_game;
+use crate::menu::stats_menu::{run_achievements, run_head_to_head, run_player_profile};
 use crate::scoreboard::scoreboard::Scoreboard;
+use crate::utils::input::read_input;
 
-pub fn run_main_menu() {
-    let mut scoreboard = Scoreboard::load();
-    let color_support = ColorSupport::Enabled;
+use chrono::Utc;
 
-    let mut maybe_saved_state: Option<GameState> = None;
+pub fn run_main_menu(storage: &dyn Storage, config: &Config) {
+    let mut scoreboard = Scoreboard::load(storage);
+    let color_support = config.color_support();
+    check_tampering(&mut scoreboard, storage, color_support);
+    check_achievements(&mut scoreboard, storage, color_support);
+    check_match_log(&mut scoreboard, storage, color_support);
 
     loop {
-        show_main_menu();
+        show_main_menu(color_support);
         let choice = ask_main_menu_choice();
 
         match choice {
             1 => {
                 // New Game
-                let state = setup_new_game(color_support);
-                let (updated_scoreboard, state_opt) =
-                    run_match(state, scoreboard.clone(), color_support);
+                let state = setup_new_game(&mut scoreboard, storage, config, color_support);
+                let match_colors = theme_for(&scoreboard, &state.player1, color_support);
+                let (updated_scoreboard, _) = run_match(state, scoreboard.clone(), storage, match_colors);
                 scoreboard = updated_scoreboard;
-                maybe_saved_state = state_opt;
             }
             2 => {
                 // Continue saved
-                match load_game_state_and_scoreboard() {
+                let Some(slot) = pick_save_slot(storage, color_support) else {
+                    continue;
+                };
+                match load_game_state_and_scoreboard(storage, &slot) {
                     Ok((state, sb)) => {
-                        let (updated_scoreboard, state_opt) =
-                            run_match(state, sb.clone(), color_support);
+                        let match_colors = theme_for(&sb, &state.player1, color_support);
+                        let (updated_scoreboard, _) = run_match(state, sb.clone(), storage, match_colors);
                         scoreboard = updated_scoreboard;
-                        maybe_saved_state = state_opt;
                     }
                     Err(e) => {
                         println!("Could not load saved game: {e}");
@@ -43,10 +58,10 @@ pub fn run_main_menu() {
             }
             3 => {
                 // View scoreboard
-                let sorted_choice = show_scoreboard_sort_menu();
+                let sorted_choice = show_scoreboard_sort_menu(color_support);
                 match sorted_choice {
                     1 => {
-                        show_scoreboard(&scoreboard);
+                        show_scoreboard(&scoreboard, color_support);
                     }
                     2 => {
                         println!("\n=== Scoreboard sorted by win rate ===");
@@ -63,21 +78,156 @@ pub fn run_main_menu() {
                                 };
                                 println!(
                                     "{:<20} {:>8} {:>8} {:>7.2}%",
-                                    name, stats.matches_played, stats.matches_won, win_rate
+                                    marked(name, stats), stats.matches_played, stats.matches_won, win_rate
                                 );
                             }
                         }
                     }
+                    3 => show_rating_leaderboard(&scoreboard, &Ruleset::Classic, color_support),
+                    4 => show_rating_leaderboard(&scoreboard, &Ruleset::Extended, color_support),
+                    5 => {
+                        scoreboard.close_rating_periods(Utc::now());
+                        let _ = scoreboard.save(storage);
+
+                        let hide = read_input("Hide provisional players? (y/n): ");
+                        let hide_provisional = matches!(hide.to_lowercase().as_str(), "y" | "yes");
+                        show_glicko_leaderboard(&scoreboard, hide_provisional, color_support);
+                    }
+                    6 => run_head_to_head(&scoreboard, storage, color_support),
+                    7 => run_player_profile(&scoreboard, color_support),
+                    8 => {
+                        show_all_time_records(&scoreboard.records, color_support);
+                        show_streak_leaders(&scoreboard, color_support);
+                    }
                     _ => {
                         println!("Invalid choice.");
                     }
                 }
             }
-            4 => {
+            4 => run_replays_menu(storage, &MatchQuery::default(), color_support),
+            5 => run_achievements(&scoreboard, storage, color_support),
+            6 => run_profiles_menu(&mut scoreboard, storage, &config.ai_name, color_support),
+            7 => {
                 println!("Goodbye!");
                 break;
             }
             _ => println!("Invalid option. Try again."),
         }
     }
-}
\ No newline at end of file
+}
+
+/* Scoreboard edited outside the game: offer the newest untouched backup, otherwise keep the flags */
+fn check_tampering(scoreboard: &mut Scoreboard, storage: &dyn Storage, color_support: ColorSupport) {
+    let Some(ids) = &scoreboard.tampering else {
+        return;
+    };
+    let names: Vec<&str> = ids.iter().map(|id| scoreboard.display_name(id)).collect();
+    show_tamper_warning("The scoreboard was", &names, color_support);
+
+    let restore = read_input("Restore the newest untouched backup? (y/n): ");
+    if matches!(restore.to_lowercase().as_str(), "y" | "yes") {
+        match storage.restore_scoreboard_backup() {
+            Ok(Some(mut restored)) => {
+                // Same as a normal load (see save_load::load_scoreboard_only)
+                restored.close_rating_periods(Utc::now());
+                *scoreboard = restored;
+                println!("{}", success("Scoreboard restored from backup.", color_support));
+                return;
+            }
+            Ok(None) => println!("{}", failure("No untouched backup found.", color_support)),
+            Err(e) => println!("{}", failure(&e, color_support)),
+        }
+    }
+
+    // Keeping the edited stats: save them signed, with the flag the leaderboards show
+    scoreboard.tampering = None;
+    if let Err(e) = scoreboard.save(storage) {
+        println!("Warning: {e}");
+    }
+}
+
+/* Achievements edited outside the game: same choice as for the scoreboard */
+fn check_achievements(scoreboard: &mut Scoreboard, storage: &dyn Storage, color_support: ColorSupport) {
+    let mut book = AchievementBook::load(storage, scoreboard);
+    let Some(keys) = book.tampering.take() else {
+        return;
+    };
+    // Older files are keyed by name
+    let ids: Vec<String> = keys
+        .iter()
+        .filter_map(|key| {
+            if scoreboard.players.contains_key(key) {
+                Some(key.clone())
+            } else {
+                scoreboard.find_profile(key).map(|p| p.id.clone())
+            }
+        })
+        .collect();
+    let names: Vec<&str> = ids.iter().map(|id| scoreboard.display_name(id)).collect();
+    show_tamper_warning("The achievements were", &names, color_support);
+
+    let restore = read_input("Restore the newest untouched backup? (y/n): ");
+    if matches!(restore.to_lowercase().as_str(), "y" | "yes") {
+        match storage.restore_achievements_backup() {
+            Ok(Some(_)) => {
+                println!("{}", success("Achievements restored from backup.", color_support));
+                return;
+            }
+            Ok(None) => println!("{}", failure("No untouched backup found.", color_support)),
+            Err(e) => println!("{}", failure(&e, color_support)),
+        }
+    }
+
+    // Keeping the edited unlocks: signed from now on, their players marked
+    scoreboard.flag_edited(ids);
+    if let Err(e) = book.save(storage).and_then(|_| scoreboard.save(storage)) {
+        println!("Warning: {e}");
+    }
+}
+
+/* Match log entries edited outside the game (left out until then): remove them, or keep them as they are */
+fn check_match_log(scoreboard: &mut Scoreboard, storage: &dyn Storage, color_support: ColorSupport) {
+    let edited = match storage.load_tampered_matches() {
+        Ok(edited) if !edited.is_empty() => edited,
+        Ok(_) => return,
+        Err(e) => {
+            println!("Warning: {e}");
+            return;
+        }
+    };
+    let mut names: Vec<&str> = edited
+        .iter()
+        .flat_map(|r| [r.player1.name.as_str(), r.player2.name.as_str()])
+        .collect();
+    names.sort();
+    names.dedup();
+    show_tamper_warning(
+        &format!("{} match log entries were", edited.len()),
+        &names,
+        color_support,
+    );
+
+    let remove = read_input("Remove them from the match log? (y/n): ");
+    if matches!(remove.to_lowercase().as_str(), "y" | "yes") {
+        match storage.remove_tampered_matches() {
+            Ok(()) => println!("{}", success("Edited entries removed.", color_support)),
+            Err(e) => println!("{}", failure(&e, color_support)),
+        }
+        return;
+    }
+
+    // Keeping them: replays and exports see them again, and everyone who played in them is marked
+    if let Err(e) = storage.accept_tampered_matches() {
+        println!("{}", failure(&e, color_support));
+        return;
+    }
+    let ids: Vec<String> = edited
+        .iter()
+        .flat_map(|r| [&r.player1, &r.player2])
+        .map(|player| scoreboard.ensure_player(player))
+        .collect();
+    scoreboard.flag_edited(ids);
+    if let Err(e) = scoreboard.save(storage) {
+        println!("Warning: {e}");
+    }
+}
@@ -1,2 +1,6 @@
 pub mod main_menu;
 pub mod settings_menu;
+pub mod replay_menu;
+pub mod stats_menu;
+pub mod profile_menu;
+pub mod save_menu;
@@ -0,0 +1,177 @@
+use crate::display::colors::{failure, info, subtle, success, ColorSupport};
+use crate::display::ui::{show_preferences, show_profile_picker};
+use crate::game::rules::Ruleset;
+use crate::io::storage::Storage;
+use crate::players::ai_difficulty::AiDifficulty;
+use crate::players::player::{is_ai_name, Player};
+use crate::players::profile::{ColorTheme, PlayerProfile};
+use crate::scoreboard::scoreboard::Scoreboard;
+use crate::scoreboard::stats::PlayerStats;
+use crate::utils::input::{read_nonempty, read_number};
+
+/* Picks (or creates) the profile a human plays as >> profile ID
+ * `taken` is the profile already in this match - nobody plays against themselves.
+ * `ai_name` is the AI's name - no human can pick it, or any of its difficulties' names.
+*/
+pub fn choose_profile(
+    scoreboard: &mut Scoreboard,
+    storage: &dyn Storage,
+    title: &str,
+    taken: Option<&str>,
+    ai_name: &str,
+    color_support: ColorSupport,
+) -> String {
+    loop {
+        if let Some(id) = pick_profile(scoreboard, storage, title, taken, ai_name, false, color_support) {
+            return id;
+        }
+    }
+}
+
+// With `allow_back` 0 returns None, otherwise it loops until a profile is chosen
+fn pick_profile(
+    scoreboard: &mut Scoreboard,
+    storage: &dyn Storage,
+    title: &str,
+    taken: Option<&str>,
+    ai_name: &str,
+    allow_back: bool,
+    color_support: ColorSupport,
+) -> Option<String> {
+    loop {
+        let ids = pickable_profiles(scoreboard, taken, ai_name);
+        let rows: Vec<(&PlayerProfile, &PlayerStats)> = ids
+            .iter()
+            .filter_map(|id| Some((scoreboard.profiles.get(id)?, scoreboard.players.get(id)?)))
+            .collect();
+        show_profile_picker(title, &rows, color_support);
+
+        let prompt = if allow_back {
+            format!("\nSelect profile (1-{}, 0 = back): ", ids.len() + 1)
+        } else {
+            format!("\nSelect profile (1-{}): ", ids.len() + 1)
+        };
+
+        match read_number(&prompt) as usize {
+            0 if allow_back => return None,
+            n if n >= 1 && n <= ids.len() => return Some(ids[n - 1].clone()),
+            n if n == ids.len() + 1 => {
+                if let Some(id) = new_profile(scoreboard, storage, taken, ai_name, color_support) {
+                    return Some(id);
+                }
+            }
+            _ => println!("{}", subtle("Invalid choice.", color_support)),
+        }
+    }
+}
+
+// Human profiles other than `taken`, most recently played first
+fn pickable_profiles(scoreboard: &Scoreboard, taken: Option<&str>, ai_name: &str) -> Vec<String> {
+    let mut profiles: Vec<&PlayerProfile> = scoreboard
+        .profiles
+        .values()
+        .filter(|p| Some(p.id.as_str()) != taken)
+        .filter(|p| !is_ai_name(&p.display_name, ai_name))
+        .collect();
+    profiles.sort_by(|a, b| {
+        b.last_played
+            .cmp(&a.last_played)
+            .then_with(|| a.display_name.to_lowercase().cmp(&b.display_name.to_lowercase()))
+    });
+    profiles.into_iter().map(|p| p.id.clone()).collect()
+}
+
+/* Asks for a name - an existing player's name (any spelling) just picks their profile */
+fn new_profile(
+    scoreboard: &mut Scoreboard,
+    storage: &dyn Storage,
+    taken: Option<&str>,
+    ai_name: &str,
+    color_support: ColorSupport,
+) -> Option<String> {
+    let name = read_nonempty("New player name >> ");
+
+    if let Some(existing) = scoreboard.find_profile(&name) {
+        let id = existing.id.clone();
+        if Some(id.as_str()) == taken {
+            println!("{}", failure(&format!("{} is already playing.", existing.display_name), color_support));
+            return None;
+        }
+        if !is_ai_name(&existing.display_name, ai_name) {
+            println!("{}", info(&format!("Welcome back, {}!", existing.display_name), color_support));
+            return Some(id);
+        }
+    }
+
+    match scoreboard.create_profile(&name, ai_name) {
+        Ok(id) => {
+            if let Err(e) = scoreboard.save(storage) {
+                println!("Warning: {e}");
+            }
+            Some(id)
+        }
+        Err(e) => {
+            println!("{}", failure(&e, color_support));
+            None
+        }
+    }
+}
+
+/* Colours for a match: Player 1's theme if they have a profile, `fallback` otherwise */
+pub fn theme_for(scoreboard: &Scoreboard, player: &Player, fallback: ColorSupport) -> ColorSupport {
+    scoreboard
+        .profile_id(player)
+        .and_then(|id| scoreboard.profiles.get(id))
+        .map_or(fallback, |p| p.preferences.color_theme.color_support())
+}
+
+/* PROFILES: pick a profile and edit its preferences */
+pub fn run_profiles_menu(
+    scoreboard: &mut Scoreboard,
+    storage: &dyn Storage,
+    ai_name: &str,
+    color_support: ColorSupport,
+) {
+    let Some(id) = pick_profile(scoreboard, storage, "👤 PROFILES", None, ai_name, true, color_support) else {
+        return;
+    };
+
+    loop {
+        let Some(profile) = scoreboard.profiles.get_mut(&id) else {
+            return;
+        };
+        show_preferences(profile, color_support);
+
+        let prefs = &mut profile.preferences;
+        match read_number("\nChange (1-3, 0 = done): ") {
+            0 => break,
+            1 => {
+                prefs.default_ruleset = match read_number("1) Classic  2) Extended  3) Ask every time: ") {
+                    1 => Some(Ruleset::Classic),
+                    2 => Some(Ruleset::Extended),
+                    _ => None,
+                }
+            }
+            2 => {
+                prefs.color_theme = match read_number("1) Neon  2) Plain (no colours): ") {
+                    2 => ColorTheme::Plain,
+                    _ => ColorTheme::Neon,
+                }
+            }
+            3 => {
+                prefs.favourite_ai = match read_number("1) Easy  2) Normal  3) Hard  4) Ask every time: ") {
+                    1 => Some(AiDifficulty::Easy),
+                    2 => Some(AiDifficulty::Normal),
+                    3 => Some(AiDifficulty::Hard),
+                    _ => None,
+                }
+            }
+            _ => println!("{}", subtle("Please enter 0-3.", color_support)),
+        }
+    }
+
+    match scoreboard.save(storage) {
+        Ok(()) => println!("{}", success("Preferences saved.", color_support)),
+        Err(e) => println!("{}", failure(&format!("Could not save preferences: {e}"), color_support)),
+    }
+}
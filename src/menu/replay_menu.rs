@@ -0,0 +1,106 @@
+use std::thread;
+use std::time::Duration;
+
+use crate::display::colors::{failure, ColorSupport};
+use crate::display::ui::{
+    show_match_victory, show_replay_header, show_replay_list, show_round_summary,
+};
+use crate::history::match_record::MatchRecord;
+use crate::history::query::MatchQuery;
+use crate::io::storage::Storage;
+use crate::utils::clear_screen::clear_screen;
+use crate::utils::input::{read_input, read_number};
+
+/* Pause between steps while autoplaying */
+const AUTOPLAY_DELAY: Duration = Duration::from_millis(1500);
+
+/* REPLAYS: pick a recorded match (passing `query`) and step through it */
+pub fn run_replays_menu(storage: &dyn Storage, query: &MatchQuery, color_support: ColorSupport) {
+    let mut records = match storage.find_matches(query) {
+        Ok(records) => records,
+        Err(e) => {
+            println!("{}", failure(&format!("Could not load replays: {e}"), color_support));
+            return;
+        }
+    };
+
+    // Newest first
+    records.reverse();
+
+    loop {
+        show_replay_list(&records, color_support);
+        if records.is_empty() {
+            return;
+        }
+
+        let choice = read_number("\nSelect match (0 = back): ") as usize;
+        match choice {
+            0 => return,
+            n if n <= records.len() => view_replay(&records[n - 1], color_support),
+            _ => println!("Invalid choice."),
+        }
+    }
+}
+
+/* Opens a replay by match ID (or the start of one) - used by the `replays <id>` subcommand */
+pub fn run_replay_by_id(storage: &dyn Storage, id: &str, color_support: ColorSupport) -> Result<(), String> {
+    let records = storage.load_matches()?;
+    let matching: Vec<&MatchRecord> = records.iter().filter(|r| r.id.starts_with(id)).collect();
+
+    match matching.as_slice() {
+        [record] => {
+            view_replay(record, color_support);
+            Ok(())
+        }
+        [] => Err(format!("No recorded match with ID '{id}'")),
+        _ => Err(format!("'{id}' matches {} recorded matches - use more of the ID", matching.len())),
+    }
+}
+
+/* Steps 1..=N are the rounds, step N+1 is the final victory screen */
+fn view_replay(record: &MatchRecord, color_support: ColorSupport) {
+    let last_step = record.rounds.len() + 1;
+    let mut step = if record.rounds.is_empty() { last_step } else { 1 };
+    let mut autoplay = false;
+    // Printed after the redraw, or the screen clear would wipe it straight away
+    let mut invalid_input = false;
+
+    loop {
+        clear_screen();
+        show_replay_header(record, step, color_support);
+        show_replay_step(record, step, color_support);
+        if invalid_input {
+            println!("{}", failure("Use n, p, a or q.", color_support));
+            invalid_input = false;
+        }
+
+        if autoplay {
+            if step < last_step {
+                thread::sleep(AUTOPLAY_DELAY);
+                step += 1;
+                continue;
+            }
+            autoplay = false;
+        }
+
+        let input = read_input("[n]ext  [p]rev  [a]utoplay  [q]uit >> ").to_lowercase();
+        match input.as_str() {
+            "" | "n" | "next" => step = (step + 1).min(last_step),
+            "p" | "prev" | "b" | "back" => step = step.saturating_sub(1).max(1),
+            "a" | "autoplay" => autoplay = true,
+            "q" | "quit" => return,
+            _ => invalid_input = true,
+        }
+    }
+}
+
+fn show_replay_step(record: &MatchRecord, step: usize, color_support: ColorSupport) {
+    if step <= record.rounds.len() {
+        let state = record.state_after(step);
+        let result = record.rounds[step - 1].result;
+        show_round_summary(&state, &result, color_support);
+    } else {
+        let state = record.state_after(record.rounds.len());
+        show_match_victory(&state, record.winner_name(), &[], color_support);
+    }
+}
@@ -0,0 +1,139 @@
+use crate::display::colors::{accent, failure, subtle, success, ColorSupport};
+use crate::display::ui::show_save_slots;
+use crate::game::game_state::GameState;
+use crate::io::save_load::{delete_save_state, list_save_slots};
+use crate::io::storage::{check_save_name, Storage};
+use crate::utils::input::{read_input, read_number};
+
+/* CONTINUE: pick a saved game (or delete some) >> slot name, None on "0 = back" */
+pub fn pick_save_slot(storage: &dyn Storage, color_support: ColorSupport) -> Option<String> {
+    loop {
+        let slots = match list_save_slots(storage) {
+            Ok(slots) => slots,
+            Err(e) => {
+                println!("{}", failure(&format!("Could not list saved games: {e}"), color_support));
+                return None;
+            }
+        };
+        show_save_slots(&slots, color_support);
+        if slots.is_empty() {
+            return None;
+        }
+
+        let choice = read_number(&format!("\nSelect save (1-{}, 0 = back): ", slots.len())) as usize;
+        let slot = match choice {
+            0 => return None,
+            n if n <= slots.len() => &slots[n - 1],
+            _ => {
+                println!("{}", subtle("Invalid choice.", color_support));
+                continue;
+            }
+        };
+
+        println!(
+            "{} Continue  {} Delete  {} Back",
+            accent("1)", color_support),
+            accent("2)", color_support),
+            accent("0)", color_support)
+        );
+        match read_number("Choice: ") {
+            // An edited save is only loaded once the player decided what to do about it
+            1 if !slot.tampered || settle_edited_slot(storage, &slot.name, color_support) => {
+                return Some(slot.name.clone());
+            }
+            2 => delete_slot(storage, &slot.name, color_support),
+            _ => {}
+        }
+    }
+}
+
+/* A save edited outside the game: put its newest untouched backup back, or play on with both players marked
+ * >> true when the slot should be loaded
+*/
+fn settle_edited_slot(storage: &dyn Storage, name: &str, color_support: ColorSupport) -> bool {
+    println!();
+    println!("{}", failure(&format!("⚠ Save '{name}' was changed outside the game."), color_support));
+    println!(
+        "{} Restore the newest untouched backup  {} Load it anyway (both players get ⚠)  {} Back",
+        accent("1)", color_support),
+        accent("2)", color_support),
+        accent("0)", color_support)
+    );
+
+    match read_number("Choice: ") {
+        1 => match storage.restore_game_state_backup(name) {
+            Ok(Some(_)) => {
+                println!("{}", success("Save restored from backup.", color_support));
+                true
+            }
+            Ok(None) => {
+                println!("{}", failure("No untouched backup found.", color_support));
+                false
+            }
+            Err(e) => {
+                println!("{}", failure(&e, color_support));
+                false
+            }
+        },
+        2 => true,
+        _ => false,
+    }
+}
+
+fn delete_slot(storage: &dyn Storage, name: &str, color_support: ColorSupport) {
+    let confirm = read_input(&format!("Delete save '{name}'? (y/n): "));
+    if !matches!(confirm.to_lowercase().as_str(), "y" | "yes") {
+        return;
+    }
+
+    match delete_save_state(storage, name) {
+        Ok(()) => println!("{}", success(&format!("Deleted '{name}'."), color_support)),
+        Err(e) => println!("{}", failure(&format!("Could not delete '{name}': {e}"), color_support)),
+    }
+}
+
+/* Asks what to call a new save - suggests "<player 1> vs <player 2>" */
+pub fn choose_slot_name(storage: &dyn Storage, state: &GameState, color_support: ColorSupport) -> String {
+    let taken = storage.list_game_states().unwrap_or_default();
+    let suggested = suggested_slot_name(state, &taken);
+
+    loop {
+        let input = read_input(&format!("Save as (ENTER = {suggested}): "));
+        let name = if input.is_empty() { suggested.clone() } else { input };
+
+        if let Err(e) = check_save_name(&name) {
+            println!("{}", failure(&format!("{e} - use letters, digits, spaces, '-' or '_'"), color_support));
+            continue;
+        }
+        if taken.contains(&name) {
+            let overwrite = read_input(&format!("'{name}' already exists. Overwrite? (y/n): "));
+            if !matches!(overwrite.to_lowercase().as_str(), "y" | "yes") {
+                continue;
+            }
+        }
+        return name;
+    }
+}
+
+// Player names cut down to what a slot name allows, numbered if that slot exists already
+fn suggested_slot_name(state: &GameState, taken: &[String]) -> String {
+    let keep = |name: &str| -> String {
+        name.chars()
+            .filter(|c| c.is_ascii_alphanumeric() || matches!(c, ' ' | '-' | '_'))
+            .take(16)
+            .collect::<String>()
+            .trim()
+            .to_string()
+    };
+    let base = format!("{} vs {}", keep(&state.player1.name), keep(&state.player2.name));
+    let base = if check_save_name(base.trim()).is_ok() {
+        base.trim().to_string()
+    } else {
+        "match".to_string()
+    };
+
+    (1..)
+        .map(|n| if n == 1 { base.clone() } else { format!("{base} {n}") })
+        .find(|name| !taken.contains(name))
+        .unwrap_or(base)
+}
@@ -1,13 +1,23 @@
 use crate::display::ascii::section_divider;
 use crate::display::colors::{accent, header, info, subtle, ColorSupport};
 use crate::game::game_state::GameState;
-use crate::game::match_settings::MatchFormat;
+use crate::game::match_settings::{BlitzSettings, MatchFormat, TimeoutPenalty, TurnTimer};
 use crate::game::rules::Ruleset;
+use crate::io::config::Config;
+use crate::io::storage::Storage;
+use crate::menu::profile_menu::choose_profile;
 use crate::players::ai_difficulty::AiDifficulty;
-use crate::players::player::{Player, PlayerType};
-use crate::utils::input::{read_nonempty, read_number};
+use crate::players::player::Player;
+use crate::players::profile::Preferences;
+use crate::scoreboard::scoreboard::Scoreboard;
+use crate::utils::input::{read_input, read_number, read_number_or};
 
-pub fn setup_new_game(color_support: ColorSupport) -> GameState {
+pub fn setup_new_game(
+    scoreboard: &mut Scoreboard,
+    storage: &dyn Storage,
+    config: &Config,
+    color_support: ColorSupport,
+) -> GameState {
     clear_screen_soft();
 
     /* Main Header for SETUP */
@@ -19,12 +29,23 @@ pub fn setup_new_game(color_support: ColorSupport) -> GameState {
     println!("{}", section_divider());
     println!();
 
-    // Player 1 Name
-    println!(
-        "{}",
-        info("🎮 Player 1 — enter your name:", color_support)
+    // Player 1 Profile
+    let p1_id = choose_profile(
+        scoreboard,
+        storage,
+        "🎮 PLAYER 1 — WHO'S PLAYING?",
+        None,
+        &config.ai_name,
+        color_support,
     );
-    let p1_name = read_nonempty(">> ");
+    let player1 = profile_player(scoreboard, &p1_id);
+    let prefs: Preferences = scoreboard.profiles[&p1_id].preferences.clone();
+
+    // From here on the setup uses Player 1's colours
+    let color_support = match color_support {
+        ColorSupport::Enabled => prefs.color_theme.color_support(),
+        ColorSupport::Disabled => ColorSupport::Disabled,
+    };
 
     println!();
     println!("{}", section_divider());
@@ -33,16 +54,8 @@ pub fn setup_new_game(color_support: ColorSupport) -> GameState {
         header(" 🎮 GAME MODE ", color_support)
     );
     println!("{}", section_divider());
-    println!(
-        "{} {}",
-        accent("1)", color_support),
-        "Single Player  (vs AI 🤖)"
-    );
-    println!(
-        "{} {}",
-        accent("2)", color_support),
-        "Multiplayer   (Local PvP 🧑‍🤝‍🧑)"
-    );
+    println!("{} Single Player  (vs AI 🤖)", accent("1)", color_support));
+    println!("{} Multiplayer   (Local PvP 🧑‍🤝‍🧑)", accent("2)", color_support));
 
     let mode = loop {
         let choice = read_number("\nSelect game mode (1-2): ");
@@ -52,8 +65,7 @@ pub fn setup_new_game(color_support: ColorSupport) -> GameState {
         }
     };
 
-    let mut player1 = Player::new_human(&p1_name);
-    let mut player2: Player;
+    let player2: Player;
 
     let ai_difficulty: Option<AiDifficulty>;
 
@@ -67,24 +79,20 @@ pub fn setup_new_game(color_support: ColorSupport) -> GameState {
                 header(" 🤖 AI DIFFICULTY ", color_support)
             );
             println!("{}", section_divider());
-            println!(
-                "{} {}",
-                accent("1)", color_support),
-                "Easy   – random choices"
-            );
-            println!(
-                "{} {}",
-                accent("2)", color_support),
-                "Normal – weighted + counters"
-            );
-            println!(
-                "{} {}",
-                accent("3)", color_support),
-                "Hard   – pattern tracking"
-            );
+            println!("{} Easy   – random choices", accent("1)", color_support));
+            println!("{} Normal – weighted + counters", accent("2)", color_support));
+            println!("{} Hard   – pattern tracking", accent("3)", color_support));
 
+            // Player 1's favourite, then the configured default
+            let suggested = prefs.favourite_ai.as_ref().or(config.defaults.difficulty.as_ref());
+            let (default, prompt) = match suggested {
+                Some(AiDifficulty::Easy) => (1, "\nSelect difficulty (1-3, ENTER = Easy): "),
+                Some(AiDifficulty::Normal) => (2, "\nSelect difficulty (1-3, ENTER = Normal): "),
+                Some(AiDifficulty::Hard) => (3, "\nSelect difficulty (1-3, ENTER = Hard): "),
+                None => (0, "\nSelect difficulty (1-3): "),
+            };
             let diff = loop {
-                let choice = read_number("\nSelect difficulty (1-3): ");
+                let choice = read_number_or(prompt, default);
                 match choice {
                     1 => break AiDifficulty::Easy,
                     2 => break AiDifficulty::Normal,
@@ -94,18 +102,19 @@ pub fn setup_new_game(color_support: ColorSupport) -> GameState {
             };
 
             ai_difficulty = Some(diff.clone());
-            player2 = Player::new_ai("Computer", diff);
+            player2 = Player::new_ai(&config.ai_name, diff);
         }
         2 => {
             // Multiplayer
-            println!();
-            println!("{}", section_divider());
-            println!(
-                "{}",
-                info("🎮 Player 2 — enter your name:", color_support)
+            let p2_id = choose_profile(
+                scoreboard,
+                storage,
+                "🎮 PLAYER 2 — WHO'S PLAYING?",
+                Some(&p1_id),
+                &config.ai_name,
+                color_support,
             );
-            let p2_name = read_nonempty(">> ");
-            player2 = Player::new_human(&p2_name);
+            player2 = profile_player(scoreboard, &p2_id);
             ai_difficulty = None;
         }
         _ => unreachable!(),
@@ -119,19 +128,18 @@ pub fn setup_new_game(color_support: ColorSupport) -> GameState {
         header(" 📜 RULESET ", color_support)
     );
     println!("{}", section_divider());
-    println!(
-        "{} {}",
-        accent("1)", color_support),
-        "Classic  (Rock, Paper, Scissors)"
-    );
-    println!(
-        "{} {}",
-        accent("2)", color_support),
-        "Extended (Rock, Paper, Scissors, Lizard, Spock)"
-    );
+    println!("{} Classic  (Rock, Paper, Scissors)", accent("1)", color_support));
+    println!("{} Extended (Rock, Paper, Scissors, Lizard, Spock)", accent("2)", color_support));
 
+    // Player 1's default, then the configured one
+    let suggested = prefs.default_ruleset.as_ref().or(config.defaults.ruleset.as_ref());
+    let (default, prompt) = match suggested {
+        Some(Ruleset::Classic) => (1, "\nSelect ruleset (1-2, ENTER = Classic): "),
+        Some(Ruleset::Extended) => (2, "\nSelect ruleset (1-2, ENTER = Extended): "),
+        None => (0, "\nSelect ruleset (1-2): "),
+    };
     let ruleset = loop {
-        let choice = read_number("\nSelect ruleset (1-2): ");
+        let choice = read_number_or(prompt, default);
         match choice {
             1 => break Ruleset::Classic,
             2 => break Ruleset::Extended,
@@ -147,24 +155,22 @@ pub fn setup_new_game(color_support: ColorSupport) -> GameState {
         header(" 🎯 MATCH FORMAT ", color_support)
     );
     println!("{}", section_divider());
-    println!(
-        "{} {}",
-        accent("1)", color_support),
-        "Single round"
-    );
-    println!(
-        "{} {}",
-        accent("2)", color_support),
-        "Best of N (N odd, e.g. 3, 5, 7)"
-    );
-    println!(
-        "{} {}",
-        accent("3)", color_support),
-        "First to K wins"
-    );
+    println!("{} Single round", accent("1)", color_support));
+    println!("{} Best of N (N odd, e.g. 3, 5, 7)", accent("2)", color_support));
+    println!("{} First to K wins", accent("3)", color_support));
 
+    // ENTER (read as 0) takes the configured format, if there is one
+    let prompt = match &config.defaults.format {
+        Some(format) => format!("\nSelect format (1-3, ENTER = {}): ", format_label(format)),
+        None => "\nSelect format (1-3): ".to_string(),
+    };
     let match_format = loop {
-        let choice = read_number("\nSelect format (1-3): ");
+        let choice = read_number_or(&prompt, 0);
+        if choice == 0
+            && let Some(format) = &config.defaults.format
+        {
+            break format.clone();
+        }
         match choice {
             1 => break MatchFormat::SingleRound,
             2 => {
@@ -193,6 +199,84 @@ pub fn setup_new_game(color_support: ColorSupport) -> GameState {
         }
     };
 
+    // Turn Timer
+    println!();
+    println!("{}", section_divider());
+    println!(
+        "{}",
+        header(" ⏱ TURN TIMER ", color_support)
+    );
+    println!("{}", section_divider());
+    println!("{} No timer", accent("1)", color_support));
+    println!("{} Per-move countdown", accent("2)", color_support));
+
+    let prompt = match config.defaults.turn_timer {
+        Some(timer) => format!(
+            "\nSelect timer (1-2, ENTER = {}s, {}): ",
+            timer.seconds,
+            penalty_label(timer.penalty)
+        ),
+        None => "\nSelect timer (1-2, ENTER = none): ".to_string(),
+    };
+    let turn_timer = loop {
+        let choice = read_number_or(&prompt, 0);
+        match choice {
+            0 => break config.defaults.turn_timer,
+            1 => break None,
+            2 => {
+                let seconds = loop {
+                    let val = read_number("Seconds per move (1+): ");
+                    if val >= 1 {
+                        break val;
+                    } else {
+                        println!("{}", subtle("The countdown must be at least 1 second.", color_support));
+                    }
+                };
+
+                println!();
+                println!("{}", info("When the time runs out:", color_support));
+                println!("{} Throw a random gesture", accent("1)", color_support));
+                println!("{} Forfeit the round", accent("2)", color_support));
+                println!("{} Forfeit the match", accent("3)", color_support));
+
+                let penalty = loop {
+                    let val = read_number("\nSelect penalty (1-3): ");
+                    match val {
+                        1 => break TimeoutPenalty::RandomGesture,
+                        2 => break TimeoutPenalty::ForfeitRound,
+                        3 => break TimeoutPenalty::ForfeitMatch,
+                        _ => println!("{}", subtle("Please enter 1, 2 or 3.", color_support)),
+                    }
+                };
+
+                break Some(TurnTimer { seconds, penalty });
+            }
+            _ => println!("{}", subtle("Please enter 1 or 2.", color_support)),
+        }
+    };
+
+    // Blitz Mode
+    println!();
+    println!("{}", section_divider());
+    println!(
+        "{}",
+        header(" ⚡ BLITZ MODE ", color_support)
+    );
+    println!("{}", section_divider());
+    println!("{} Off", accent("1)", color_support));
+    println!("{} On   (ties go to the faster throw)", accent("2)", color_support));
+    println!("{} On + bonus point for sub-second wins", accent("3)", color_support));
+
+    let blitz = loop {
+        let choice = read_number("\nSelect blitz mode (1-3): ");
+        match choice {
+            1 => break None,
+            2 => break Some(BlitzSettings { sub_second_bonus: false }),
+            3 => break Some(BlitzSettings { sub_second_bonus: true }),
+            _ => println!("{}", subtle("Please enter 1, 2 or 3.", color_support)),
+        }
+    };
+
     println!();
     println!("{}", section_divider());
     println!(
@@ -216,6 +300,21 @@ pub fn setup_new_game(color_support: ColorSupport) -> GameState {
         accent("Format:", color_support),
         match_format
     );
+    if let Some(timer) = turn_timer {
+        println!(
+            "{} {}s per move ({:?} on timeout)",
+            accent("Turn Timer:", color_support),
+            timer.seconds,
+            timer.penalty
+        );
+    }
+    if let Some(blitz) = blitz {
+        println!(
+            "{} On{}",
+            accent("Blitz:", color_support),
+            if blitz.sub_second_bonus { " (sub-second bonus)" } else { "" }
+        );
+    }
     if let Some(diff) = ai_difficulty {
         println!(
             "{} {:?}",
@@ -225,22 +324,35 @@ pub fn setup_new_game(color_support: ColorSupport) -> GameState {
     }
     println!();
     println!("{}", subtle("Press ENTER to start the match...", color_support));
-    let _ = read_input_silent();
+    let _ = read_input("");
 
-    GameState::new(player1, player2, ruleset, match_format)
+    GameState::new(player1, player2, ruleset, match_format, turn_timer, blitz)
 }
 
+fn format_label(format: &MatchFormat) -> String {
+    match format {
+        MatchFormat::SingleRound => "single round".to_string(),
+        MatchFormat::BestOf(n) => format!("best of {n}"),
+        MatchFormat::FirstTo(k) => format!("first to {k}"),
+    }
+}
 
-fn clear_screen_soft() {
-    /* We don't use full clear to keep neon banner */
-    println!("\n\n");
+fn penalty_label(penalty: TimeoutPenalty) -> &'static str {
+    match penalty {
+        TimeoutPenalty::RandomGesture => "random gesture on timeout",
+        TimeoutPenalty::ForfeitRound => "forfeit the round on timeout",
+        TimeoutPenalty::ForfeitMatch => "forfeit the match on timeout",
+    }
 }
 
-/* Waiting for ENTER without any message */
-fn read_input_silent() -> String {
-    use std::io::{self, Read};
+// A human player bound to a scoreboard profile
+fn profile_player(scoreboard: &Scoreboard, id: &str) -> Player {
+    let mut player = Player::new_human(scoreboard.display_name(id));
+    player.profile_id = Some(id.to_string());
+    player
+}
 
-    let mut s = String::new();
-    let _ = io::stdin().read_line(&mut s);
-    s
+fn clear_screen_soft() {
+    /* We don't use full clear to keep neon banner */
+    println!("\n\n");
 }
@@ -0,0 +1,84 @@
+use crate::display::colors::ColorSupport;
+use crate::achievements::tracker::AchievementBook;
+use crate::display::ui::{
+    show_achievements, show_head_to_head, show_player_picker, show_player_profile,
+};
+use crate::history::analytics::gesture_pairings;
+use crate::io::storage::Storage;
+use crate::scoreboard::scoreboard::Scoreboard;
+use crate::utils::input::read_number;
+
+/* HEAD-TO-HEAD: pick two players and compare them */
+pub fn run_head_to_head(scoreboard: &Scoreboard, storage: &dyn Storage, color_support: ColorSupport) {
+    let Some(player) = pick_player(scoreboard, "First player", color_support) else {
+        return;
+    };
+    let Some(opponent) = pick_player(scoreboard, "Second player", color_support) else {
+        return;
+    };
+
+    if player == opponent {
+        println!("Pick two different players.");
+        return;
+    }
+
+    let records = storage.load_matches().unwrap_or_else(|e| {
+        println!("Warning: {e}");
+        Vec::new()
+    });
+    let pairings = gesture_pairings(&records, scoreboard, &player, &opponent);
+
+    show_head_to_head(
+        scoreboard.display_name(&player),
+        scoreboard.display_name(&opponent),
+        scoreboard.head_to_head(&player, &opponent),
+        scoreboard.head_to_head(&opponent, &player),
+        &pairings,
+        color_support,
+    );
+}
+
+/* PLAYER PROFILE: lifetime gesture tendencies of one player */
+pub fn run_player_profile(scoreboard: &Scoreboard, color_support: ColorSupport) {
+    let Some(id) = pick_player(scoreboard, "Player profile", color_support) else {
+        return;
+    };
+
+    if let Some(stats) = scoreboard.players.get(&id) {
+        show_player_profile(scoreboard.display_name(&id), stats, color_support);
+    }
+}
+
+/* ACHIEVEMENTS: browse one player's unlocks */
+pub fn run_achievements(scoreboard: &Scoreboard, storage: &dyn Storage, color_support: ColorSupport) {
+    let Some(id) = pick_player(scoreboard, "Achievements", color_support) else {
+        return;
+    };
+
+    let book = AchievementBook::load(storage, scoreboard);
+    show_achievements(scoreboard.display_name(&id), book.unlocks_for(&id), color_support);
+}
+
+/* Numbered list of every player on the scoreboard >> profile ID, None on "0 = back" */
+fn pick_player(scoreboard: &Scoreboard, title: &str, color_support: ColorSupport) -> Option<String> {
+    let mut players: Vec<(&str, &str)> = scoreboard
+        .players
+        .keys()
+        .map(|id| (id.as_str(), scoreboard.display_name(id)))
+        .collect();
+    players.sort_by_key(|(_, name)| name.to_lowercase());
+
+    let names: Vec<&str> = players.iter().map(|(_, name)| *name).collect();
+    show_player_picker(title, &names, color_support);
+    if players.is_empty() {
+        return None;
+    }
+
+    loop {
+        match read_number("Select player (0 = back): ") as usize {
+            0 => return None,
+            n if n <= players.len() => return Some(players[n - 1].0.to_string()),
+            _ => println!("Invalid choice."),
+        }
+    }
+}
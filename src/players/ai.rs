@@ -18,6 +18,16 @@ pub fn generate_ai_move(
     }
 }
 
+/* Blitz: Simulated Thinking Time, so Ties vs AI can be Broken on Speed too */
+pub fn ai_reaction_ms(difficulty: &AiDifficulty) -> u64 {
+    let mut rng = thread_rng();
+    match difficulty {
+        AiDifficulty::Easy => rng.gen_range(1200..2500),
+        AiDifficulty::Normal => rng.gen_range(800..1800),
+        AiDifficulty::Hard => rng.gen_range(500..1200),
+    }
+}
+
 // EASY
 fn generate_easy_move(ruleset: &Ruleset) -> Gesture {
     let allowed = Gesture::gestures_for_ruleset(ruleset);
@@ -1,4 +1,4 @@
 pub mod player;
 pub mod ai;
 pub mod ai_difficulty;
-
+pub mod profile;
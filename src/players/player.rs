@@ -1,6 +1,10 @@
 use serde::{Deserialize, Serialize};
 
 use super::ai_difficulty::AiDifficulty;
+use super::profile::normalize_name;
+
+/* Name AI opponents play under unless the config says otherwise (`ai_name`) */
+pub const DEFAULT_AI_NAME: &str = "Computer";
 
 // Type of Player :: Human or Computer with Difficulty
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -13,6 +17,10 @@ pub enum PlayerType {
 pub struct Player {
     pub name: String,
     pub player_type: PlayerType,
+
+    // Scoreboard profile this player plays as (None until the first finished match)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile_id: Option<String>,
 }
 
 impl Player {
@@ -20,14 +28,35 @@ impl Player {
         Self {
             name: name.to_string(),
             player_type: PlayerType::Human,
+            profile_id: None,
         }
     }
 
-    pub fn new_ai(name: &str, difficulty: AiDifficulty) -> Self {
+    /* Each difficulty plays as its own player ("Computer (Hard)"), with its own profile and ratings */
+    pub fn new_ai(ai_name: &str, difficulty: AiDifficulty) -> Self {
         Self {
-            name: name.to_string(),
+            name: ai_player_name(ai_name, &difficulty),
             player_type: PlayerType::Computer(difficulty),
+            profile_id: None,
         }
     }
 }
 
+fn ai_player_name(ai_name: &str, difficulty: &AiDifficulty) -> String {
+    let level = match difficulty {
+        AiDifficulty::Easy => "Easy",
+        AiDifficulty::Normal => "Normal",
+        AiDifficulty::Hard => "Hard",
+    };
+    format!("{ai_name} ({level})")
+}
+
+/* `name` belongs to the AI: one of its difficulties, or its plain name (what older saves played as) */
+pub fn is_ai_name(name: &str, ai_name: &str) -> bool {
+    let wanted = normalize_name(name);
+    [AiDifficulty::Easy, AiDifficulty::Normal, AiDifficulty::Hard]
+        .iter()
+        .map(|difficulty| ai_player_name(ai_name, difficulty))
+        .chain(std::iter::once(ai_name.to_string()))
+        .any(|taken| normalize_name(&taken) == wanted)
+}
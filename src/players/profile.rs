@@ -0,0 +1,87 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::ai_difficulty::AiDifficulty;
+use crate::display::colors::ColorSupport;
+use crate::game::rules::Ruleset;
+use crate::utils::id::new_id;
+
+/* A Person Behind One or More Names
+ * Stats are keyed by `id`, so renaming or merging never loses history.
+*/
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerProfile {
+    pub id: String,
+    pub display_name: String,
+
+    // Earlier names (after a rename / merge) that still resolve to this profile
+    #[serde(default)]
+    pub aliases: Vec<String>,
+
+    // When this profile last finished a match
+    #[serde(default)]
+    pub last_played: Option<DateTime<Utc>>,
+
+    #[serde(default)]
+    pub preferences: Preferences,
+}
+
+/* What a player usually picks - offered as the default during match setup */
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Preferences {
+    pub default_ruleset: Option<Ruleset>,
+    pub color_theme: ColorTheme,
+    pub favourite_ai: Option<AiDifficulty>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColorTheme {
+    #[default]
+    Neon,
+    Plain,
+}
+
+impl ColorTheme {
+    pub fn color_support(self) -> ColorSupport {
+        match self {
+            ColorTheme::Neon => ColorSupport::Enabled,
+            ColorTheme::Plain => ColorSupport::Disabled,
+        }
+    }
+}
+
+impl PlayerProfile {
+    pub fn new(display_name: &str) -> Self {
+        Self {
+            id: new_id(),
+            display_name: clean_name(display_name),
+            aliases: Vec::new(),
+            last_played: None,
+            preferences: Preferences::default(),
+        }
+    }
+
+    // Display name or any alias, ignoring case and spacing
+    pub fn answers_to(&self, name: &str) -> bool {
+        let wanted = normalize_name(name);
+        normalize_name(&self.display_name) == wanted
+            || self.aliases.iter().any(|a| normalize_name(a) == wanted)
+    }
+
+    // Keeps `name` as an alias unless the profile already answers to it
+    pub fn add_alias(&mut self, name: &str) {
+        if !self.answers_to(name) {
+            self.aliases.push(clean_name(name));
+        }
+    }
+}
+
+/* "  Haris   K " >> "Haris K" (spacing cleaned, case kept) */
+pub fn clean_name(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/* "  HARIS   k " >> "haris k" (what names are compared by) */
+pub fn normalize_name(name: &str) -> String {
+    clean_name(name).to_lowercase()
+}
@@ -0,0 +1,438 @@
+pub mod svg;
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use chrono::{DateTime, Local, TimeZone, Utc};
+
+use crate::game::rules::{Gesture, Ruleset};
+use crate::history::match_record::MatchRecord;
+use crate::io::export::{match_rows, standings};
+use crate::scoreboard::scoreboard::Scoreboard;
+use svg::{Series, gesture_bar, gesture_color, line_chart};
+
+/* League Report (one self-contained HTML page for the intranet share)
+ * Inline CSS and SVG only - no scripts, fonts or anything else fetched, so it opens straight from a file.
+ * Leaderboard, Elo over time, head-to-head matrix, gesture habits and the latest matches.
+*/
+
+// Players in the charts and the head-to-head matrix (most matches first)
+const MAX_PLAYERS: usize = 10;
+const RECENT_MATCHES: usize = 20;
+
+const STYLE: &str = "
+body { background: #0d0221; color: #e0e0ff; font-family: system-ui, sans-serif; margin: 2rem auto; max-width: 1000px; padding: 0 1rem; }
+h1 { color: #ff2e88; margin-bottom: 0; }
+h2 { color: #00e5ff; border-bottom: 1px solid #2a1b5c; padding-bottom: .3rem; margin-top: 2.5rem; }
+.meta, .empty { color: #8a80b0; }
+table { border-collapse: collapse; width: 100%; font-size: .95rem; }
+th, td { padding: .35rem .6rem; text-align: right; border-bottom: 1px solid #2a1b5c; }
+th:first-child, td:first-child, td.name { text-align: left; }
+th { color: #b388ff; font-weight: 600; }
+tr:hover td { background: #1a0b3d; }
+.edited { color: #ffd600; cursor: help; }
+.matrix td { text-align: center; }
+.matrix td.self { background: #1a0b3d; }
+.chart { width: 100%; height: auto; background: #120633; border-radius: 6px; }
+.chart .grid { stroke: #2a1b5c; }
+.chart .axis, .chart .legend { fill: #8a80b0; font-size: 11px; }
+.chart .legend { fill: #e0e0ff; }
+.swatch { display: inline-block; width: .8em; height: .8em; margin: 0 .3em 0 1em; vertical-align: middle; }
+.winner { color: #7cff4f; font-weight: 600; }
+";
+
+pub fn league_report(
+    scoreboard: &Scoreboard,
+    records: &[MatchRecord],
+    generated_at: DateTime<Utc>,
+) -> String {
+    let players = scoreboard
+        .players
+        .values()
+        .filter(|s| s.matches_played > 0)
+        .count();
+    let generated = generated_at.with_timezone(&Local).format("%Y-%m-%d %H:%M");
+
+    let mut page = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>League Report</title>\n<style>{STYLE}</style>\n</head>\n<body>\n\
+         <h1>League Report</h1>\n<p class=\"meta\">Generated {generated} · {players} players · {} matches</p>\n",
+        records.len()
+    );
+    page += &leaderboard(scoreboard);
+    page += &rating_charts(scoreboard, records);
+    page += &head_to_head(scoreboard);
+    page += &gesture_habits(scoreboard);
+    page += &recent_matches(scoreboard, records);
+    page + "</body>\n</html>\n"
+}
+
+/* Text for HTML/SVG content and attributes */
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+const EMPTY: &str = "<p class=\"empty\">No matches recorded yet.</p>\n";
+
+// Profile IDs of the most active players
+fn top_players(scoreboard: &Scoreboard) -> Vec<&str> {
+    let mut ids: Vec<(&str, u32)> = scoreboard
+        .players
+        .iter()
+        .filter(|(_, s)| s.matches_played > 0)
+        .map(|(id, s)| (id.as_str(), s.matches_played))
+        .collect();
+    ids.sort_by(|(a, a_played), (b, b_played)| {
+        b_played
+            .cmp(a_played)
+            .then_with(|| scoreboard.display_name(a).cmp(scoreboard.display_name(b)))
+    });
+    ids.into_iter()
+        .take(MAX_PLAYERS)
+        .map(|(id, _)| id)
+        .collect()
+}
+
+fn leaderboard(scoreboard: &Scoreboard) -> String {
+    let mut html = String::from("<h2>Leaderboard</h2>\n");
+    let rows = standings(scoreboard, None);
+    if rows.iter().all(|r| r.matches == 0) {
+        return html + EMPTY;
+    }
+
+    html += "<table>\n<tr><th>#</th><th>Player</th><th>Matches</th><th>Wins</th><th>Win %</th>\
+             <th>Rounds won</th><th>Elo Classic</th><th>Elo Extended</th><th>Glicko-2</th></tr>\n";
+    let rating = |r: Option<f64>| r.map(|r| format!("{r:.0}")).unwrap_or_default();
+    for (rank, row) in rows.iter().filter(|r| r.matches > 0).enumerate() {
+        let edited = if row.edited {
+            " <span class=\"edited\" title=\"Stats were changed outside the game\">⚠</span>"
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td class=\"name\">{}{edited}</td><td>{}</td><td>{}</td><td>{:.1}</td>\
+             <td>{}</td><td>{}</td><td>{}</td><td>{:.0} ± {:.0}</td></tr>",
+            rank + 1,
+            escape(&row.player),
+            row.matches,
+            row.wins,
+            row.win_rate,
+            row.rounds_won,
+            rating(row.elo_classic),
+            rating(row.elo_extended),
+            row.glicko,
+            row.glicko_deviation,
+        );
+    }
+    html + "</table>\n"
+}
+
+// One player's Elo after each of their matches (when, rating)
+type RatingLine = Vec<(DateTime<Utc>, f64)>;
+
+/* Elo after every match, replayed from the match log >> ruleset >> profile ID >> rating line */
+fn rating_history(
+    scoreboard: &Scoreboard,
+    records: &[MatchRecord],
+) -> HashMap<Ruleset, HashMap<String, RatingLine>> {
+    let mut replay = scoreboard.replayed(&[]);
+    let mut ordered: Vec<&MatchRecord> = records.iter().collect();
+    ordered.sort_by_key(|r| r.played_at);
+
+    let mut history: HashMap<Ruleset, HashMap<String, RatingLine>> = HashMap::new();
+    for record in ordered {
+        replay.update_after_match(record);
+        for player in [&record.player1, &record.player2] {
+            let Some(id) = replay.profile_id(player) else {
+                continue;
+            };
+            let rating = replay.players[id].rating(&record.ruleset);
+            history
+                .entry(record.ruleset.clone())
+                .or_default()
+                .entry(id.to_string())
+                .or_default()
+                .push((record.played_at, rating));
+        }
+    }
+    history
+}
+
+fn rating_charts(scoreboard: &Scoreboard, records: &[MatchRecord]) -> String {
+    let mut html = String::from("<h2>Elo Over Time</h2>\n");
+    let history = rating_history(scoreboard, records);
+    if history.is_empty() {
+        return html + EMPTY;
+    }
+
+    for ruleset in [Ruleset::Classic, Ruleset::Extended] {
+        let Some(by_player) = history.get(&ruleset) else {
+            continue;
+        };
+        let mut players: Vec<(&String, &RatingLine)> = by_player.iter().collect();
+        players.sort_by(|(a, a_points), (b, b_points)| {
+            b_points
+                .len()
+                .cmp(&a_points.len())
+                .then_with(|| scoreboard.display_name(a).cmp(scoreboard.display_name(b)))
+        });
+
+        let series: Vec<Series> = players
+            .into_iter()
+            .take(MAX_PLAYERS)
+            .map(|(id, points)| Series {
+                label: scoreboard.display_name(id).to_string(),
+                points: points
+                    .iter()
+                    .map(|(at, rating)| (at.timestamp() as f64, *rating))
+                    .collect(),
+            })
+            .collect();
+
+        let day = |secs: f64| {
+            Utc.timestamp_opt(secs as i64, 0)
+                .single()
+                .map(|at| at.with_timezone(&Local).format("%Y-%m-%d").to_string())
+                .unwrap_or_default()
+        };
+        let first = series
+            .iter()
+            .filter_map(|s| s.points.first())
+            .map(|p| p.0)
+            .fold(f64::MAX, f64::min);
+        let last = series
+            .iter()
+            .filter_map(|s| s.points.last())
+            .map(|p| p.0)
+            .fold(f64::MIN, f64::max);
+
+        let _ = writeln!(
+            html,
+            "<h3>{ruleset:?}</h3>\n{}",
+            line_chart(&series, (&day(first), &day(last)))
+        );
+    }
+    html
+}
+
+/* Row player's match record against each column player (wins–losses, ties if any) */
+fn head_to_head(scoreboard: &Scoreboard) -> String {
+    let mut html = String::from("<h2>Head-to-Head</h2>\n");
+    let players = top_players(scoreboard);
+    if players.is_empty() {
+        return html + EMPTY;
+    }
+
+    html += "<table class=\"matrix\">\n<tr><th></th>";
+    for id in &players {
+        let _ = write!(html, "<th>{}</th>", escape(scoreboard.display_name(id)));
+    }
+    html += "</tr>\n";
+
+    for row in &players {
+        let _ = write!(
+            html,
+            "<tr><td class=\"name\">{}</td>",
+            escape(scoreboard.display_name(row))
+        );
+        for column in &players {
+            if row == column {
+                html += "<td class=\"self\"></td>";
+                continue;
+            }
+            let Some(record) = scoreboard
+                .head_to_head(row, column)
+                .filter(|r| r.matches_played > 0)
+            else {
+                html += "<td></td>";
+                continue;
+            };
+
+            let ties = record.matches_played - record.matches_won - record.matches_lost;
+            let text = if ties > 0 {
+                format!("{}–{}–{ties}", record.matches_won, record.matches_lost)
+            } else {
+                format!("{}–{}", record.matches_won, record.matches_lost)
+            };
+            // Red (always lost) through green (always won)
+            let share = record.matches_won as f64 / record.matches_played as f64;
+            let _ = write!(
+                html,
+                "<td style=\"background: hsl({:.0}, 60%, 22%)\">{text}</td>",
+                share * 120.0
+            );
+        }
+        html += "</tr>\n";
+    }
+    html + "</table>\n"
+}
+
+fn gesture_habits(scoreboard: &Scoreboard) -> String {
+    let mut html = String::from("<h2>Gesture Habits</h2>\n");
+    let players = top_players(scoreboard);
+    let all = Gesture::gestures_for_ruleset(&Ruleset::Extended);
+    if players.is_empty() {
+        return html + EMPTY;
+    }
+
+    html += "<p class=\"meta\">";
+    for gesture in &all {
+        let _ = write!(
+            html,
+            "<span class=\"swatch\" style=\"background: {}\"></span>{}",
+            gesture_color(gesture),
+            gesture.as_str()
+        );
+    }
+    html += "</p>\n<table>\n<tr><th>Player</th><th>Throws</th><th>Favourite</th><th></th></tr>\n";
+
+    for id in players {
+        let tendencies = &scoreboard.players[id].tendencies;
+        let counts: Vec<(Gesture, u32)> = all
+            .iter()
+            .map(|g| (g.clone(), tendencies.counts.get(g).copied().unwrap_or(0)))
+            .collect();
+        let total: u32 = counts.iter().map(|(_, n)| n).sum();
+        let favourite = counts
+            .iter()
+            .filter(|(_, n)| *n > 0)
+            .max_by_key(|(_, n)| *n)
+            .map_or("", |(g, _)| g.as_str());
+
+        let _ = writeln!(
+            html,
+            "<tr><td class=\"name\">{}</td><td>{total}</td><td>{favourite}</td><td>{}</td></tr>",
+            escape(scoreboard.display_name(id)),
+            gesture_bar(&counts)
+        );
+    }
+    html + "</table>\n"
+}
+
+fn recent_matches(scoreboard: &Scoreboard, records: &[MatchRecord]) -> String {
+    let mut html = String::from("<h2>Recent Matches</h2>\n");
+    let mut rows = match_rows(scoreboard, records);
+    if rows.is_empty() {
+        return html + EMPTY;
+    }
+    rows.sort_by_key(|row| std::cmp::Reverse(row.played_at));
+
+    html += "<table>\n<tr><th>Played</th><th>Player 1</th><th>Score</th><th>Player 2</th>\
+             <th>Ruleset</th><th>Format</th></tr>\n";
+    for row in rows.iter().take(RECENT_MATCHES) {
+        let name = |player: &str| {
+            if row.winner.as_deref() == Some(player) {
+                format!("<span class=\"winner\">{}</span>", escape(player))
+            } else {
+                escape(player)
+            }
+        };
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td class=\"name\">{}</td><td>{}–{}</td><td class=\"name\">{}</td>\
+             <td>{:?}</td><td>{}</td></tr>",
+            row.played_at.with_timezone(&Local).format("%Y-%m-%d %H:%M"),
+            name(&row.player1),
+            row.score_player1,
+            row.score_player2,
+            name(&row.player2),
+            row.ruleset,
+            escape(&row.match_format),
+        );
+    }
+    html + "</table>\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::game_state::GameState;
+    use crate::game::match_settings::MatchFormat;
+    use crate::game::round::{resolve_round, Throw};
+    use crate::players::player::Player;
+    use crate::scoreboard::stats::MatchOutcome;
+
+    // One-round Classic match that Player 1 wins
+    fn won(scoreboard: &mut Scoreboard, winner: &str, loser: &str) -> MatchRecord {
+        let mut state = GameState::new(
+            Player::new_human(winner),
+            Player::new_human(loser),
+            Ruleset::Classic,
+            MatchFormat::SingleRound,
+            None,
+            None,
+        );
+        let throw = |gesture| Throw { gesture, reaction_ms: None };
+        resolve_round(&mut state, throw(Gesture::Rock), throw(Gesture::Scissors));
+        let record = MatchRecord::from_state(&state, MatchOutcome::Player1Win);
+        scoreboard.update_after_match(&record);
+        record
+    }
+
+    #[test]
+    fn an_empty_league_still_makes_a_page() {
+        let page = league_report(&Scoreboard::new(), &[], Utc::now());
+
+        assert!(page.starts_with("<!DOCTYPE html>") && page.ends_with("</html>\n"));
+        assert_eq!(page.matches(EMPTY).count(), 5);
+        assert!(page.contains("0 players · 0 matches"));
+    }
+
+    #[test]
+    fn report_is_self_contained_and_escaped() {
+        let mut scoreboard = Scoreboard::new();
+        let records = [
+            won(&mut scoreboard, "Ana", "<script>alert(1)</script>"),
+            won(&mut scoreboard, "Ana", "Ben & Co"),
+        ];
+        let page = league_report(&scoreboard, &records, Utc::now());
+
+        assert!(!page.contains("<script") && !page.contains("src=") && !page.contains("href="));
+        assert!(page.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+        assert!(page.contains("Ben &amp; Co"));
+        assert!(page.contains("3 players · 2 matches"));
+        // One chart for Classic, none for Extended
+        assert_eq!(page.matches("<svg class=\"chart\"").count(), 1);
+        assert!(!page.contains("<h3>Extended</h3>"));
+    }
+
+    #[test]
+    fn head_to_head_matrix_reads_from_the_row_player() {
+        let mut scoreboard = Scoreboard::new();
+        won(&mut scoreboard, "Ana", "Ben");
+        won(&mut scoreboard, "Ana", "Ben");
+        won(&mut scoreboard, "Ben", "Ana");
+        won(&mut scoreboard, "Cid", "Ana");
+
+        let html = head_to_head(&scoreboard);
+        let row = |name: &str| {
+            let start = html.find(&format!("<tr><td class=\"name\">{name}</td>")).unwrap();
+            html[start..start + html[start..].find("</tr>").unwrap()].to_string()
+        };
+        // Columns: Ana (4 matches), Ben (3), Cid (1)
+        assert!(row("Ana").contains(">2–1</td>") && row("Ana").contains(">0–1</td>"));
+        // Ben and Cid never met
+        assert!(row("Ben").ends_with(">1–2</td><td class=\"self\"></td><td></td>"));
+        assert!(row("Cid").ends_with("<td></td><td class=\"self\"></td>"));
+    }
+
+    #[test]
+    fn rating_history_follows_each_players_elo() {
+        let mut scoreboard = Scoreboard::new();
+        let records = [won(&mut scoreboard, "Ana", "Ben"), won(&mut scoreboard, "Ana", "Ben")];
+        let history = rating_history(&scoreboard, &records);
+
+        let ana = scoreboard.find_profile("Ana").unwrap().id.clone();
+        let ratings: Vec<f64> = history[&Ruleset::Classic][&ana].iter().map(|(_, r)| *r).collect();
+        assert_eq!(ratings.len(), 2);
+        assert_eq!(ratings[0], 1216.0);
+        assert_eq!(*ratings.last().unwrap(), scoreboard.players[&ana].rating(&Ruleset::Classic));
+        assert!(!history.contains_key(&Ruleset::Extended));
+    }
+}
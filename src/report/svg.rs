@@ -0,0 +1,221 @@
+use std::fmt::Write as _;
+
+use super::escape;
+use crate::game::rules::Gesture;
+
+/* Inline SVG Charts (plain markup, no scripts - the page has to work straight off a file share) */
+const PALETTE: [&str; 10] = [
+    "#ff2e88", "#00e5ff", "#ffd600", "#7cff4f", "#b388ff", "#ff9100", "#18ffff", "#ff5252",
+    "#69f0ae", "#ea80fc",
+];
+
+pub fn series_color(index: usize) -> &'static str {
+    PALETTE[index % PALETTE.len()]
+}
+
+pub fn gesture_color(gesture: &Gesture) -> &'static str {
+    match gesture {
+        Gesture::Rock => "#ff5252",
+        Gesture::Paper => "#00e5ff",
+        Gesture::Scissors => "#ffd600",
+        Gesture::Lizard => "#7cff4f",
+        Gesture::Spock => "#b388ff",
+    }
+}
+
+/* One line of a line chart: (x, y) points, x ascending */
+pub struct Series {
+    pub label: String,
+    pub points: Vec<(f64, f64)>,
+}
+
+const WIDTH: f64 = 760.0;
+const HEIGHT: f64 = 300.0;
+const LEFT: f64 = 50.0;
+const RIGHT: f64 = 170.0;
+const TOP: f64 = 15.0;
+const BOTTOM: f64 = 30.0;
+
+/* Line chart with a legend, both axes scaled to the data (`x_labels` go under the start and end) */
+pub fn line_chart(series: &[Series], x_labels: (&str, &str)) -> String {
+    let points = || series.iter().flat_map(|s| s.points.iter());
+    let (min_x, max_x) = bounds(points().map(|p| p.0));
+    let (min_y, max_y) = bounds(points().map(|p| p.1));
+    // Some headroom, and never a flat range
+    let (min_y, max_y) = if max_y - min_y < 50.0 {
+        let mid = (min_y + max_y) / 2.0;
+        (mid - 25.0, mid + 25.0)
+    } else {
+        (min_y - 10.0, max_y + 10.0)
+    };
+
+    let plot_w = WIDTH - LEFT - RIGHT;
+    let plot_h = HEIGHT - TOP - BOTTOM;
+    let x = |v: f64| {
+        if max_x > min_x {
+            LEFT + (v - min_x) / (max_x - min_x) * plot_w
+        } else {
+            LEFT + plot_w / 2.0
+        }
+    };
+    let y = |v: f64| TOP + (max_y - v) / (max_y - min_y) * plot_h;
+
+    let mut svg = format!(
+        r#"<svg class="chart" viewBox="0 0 {WIDTH} {HEIGHT}" xmlns="http://www.w3.org/2000/svg" role="img">"#
+    );
+
+    for step in 0..=4 {
+        let value = min_y + (max_y - min_y) * step as f64 / 4.0;
+        let _ = write!(
+            svg,
+            r#"<line class="grid" x1="{LEFT}" y1="{py:.1}" x2="{x2:.1}" y2="{py:.1}"/><text class="axis" x="{tx:.1}" y="{ty:.1}" text-anchor="end">{value:.0}</text>"#,
+            py = y(value),
+            x2 = LEFT + plot_w,
+            tx = LEFT - 6.0,
+            ty = y(value) + 4.0,
+        );
+    }
+    let _ = write!(
+        svg,
+        r#"<text class="axis" x="{LEFT}" y="{ly:.1}">{}</text><text class="axis" x="{rx:.1}" y="{ly:.1}" text-anchor="end">{}</text>"#,
+        escape(x_labels.0),
+        escape(x_labels.1),
+        ly = HEIGHT - 8.0,
+        rx = LEFT + plot_w,
+    );
+
+    for (i, line) in series.iter().enumerate() {
+        let color = series_color(i);
+        let coords: Vec<String> = line
+            .points
+            .iter()
+            .map(|&(px, py)| format!("{:.1},{:.1}", x(px), y(py)))
+            .collect();
+        let _ = write!(
+            svg,
+            r#"<polyline fill="none" stroke="{color}" stroke-width="2" points="{}"><title>{}</title></polyline>"#,
+            coords.join(" "),
+            escape(&line.label)
+        );
+        if let Some(&(px, py)) = line.points.last() {
+            let _ = write!(
+                svg,
+                r#"<circle cx="{:.1}" cy="{:.1}" r="3" fill="{color}"><title>{} {py:.0}</title></circle>"#,
+                x(px),
+                y(py),
+                escape(&line.label)
+            );
+        }
+
+        let legend_y = TOP + 10.0 + i as f64 * 18.0;
+        let _ = write!(
+            svg,
+            r#"<rect x="{lx:.1}" y="{ry:.1}" width="10" height="10" fill="{color}"/><text class="legend" x="{tx:.1}" y="{legend_y:.1}">{}</text>"#,
+            escape(&line.label),
+            lx = WIDTH - RIGHT + 15.0,
+            ry = legend_y - 9.0,
+            tx = WIDTH - RIGHT + 30.0,
+        );
+    }
+
+    svg + "</svg>"
+}
+
+fn bounds(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    values.fold((f64::MAX, f64::MIN), |(lo, hi), v| (lo.min(v), hi.max(v)))
+}
+
+/* Horizontal bar split by how often each gesture was thrown */
+pub fn gesture_bar(counts: &[(Gesture, u32)]) -> String {
+    const BAR_W: f64 = 320.0;
+    const BAR_H: f64 = 18.0;
+
+    let total: u32 = counts.iter().map(|(_, n)| n).sum();
+    let mut svg = format!(
+        r#"<svg class="bar" viewBox="0 0 {BAR_W} {BAR_H}" width="{BAR_W}" height="{BAR_H}" xmlns="http://www.w3.org/2000/svg">"#
+    );
+    if total == 0 {
+        return svg + "</svg>";
+    }
+
+    let mut offset = 0.0;
+    for (gesture, count) in counts.iter().filter(|(_, n)| *n > 0) {
+        let share = *count as f64 / total as f64;
+        let _ = write!(
+            svg,
+            r#"<rect x="{offset:.1}" y="0" width="{w:.1}" height="{BAR_H}" fill="{}"><title>{} {:.0}% ({count})</title></rect>"#,
+            gesture_color(gesture),
+            gesture.as_str(),
+            share * 100.0,
+            w = share * BAR_W,
+        );
+        offset += share * BAR_W;
+    }
+    svg + "</svg>"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The `points` attribute of every polyline
+    fn polylines(svg: &str) -> Vec<Vec<(f64, f64)>> {
+        svg.split("points=\"")
+            .skip(1)
+            .map(|rest| {
+                rest[..rest.find('"').unwrap()]
+                    .split(' ')
+                    .map(|xy| {
+                        let (x, y) = xy.split_once(',').unwrap();
+                        (x.parse().unwrap(), y.parse().unwrap())
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn line_chart_scales_every_series_into_the_plot() {
+        let series = [
+            Series { label: "Ana".into(), points: vec![(0.0, 1200.0), (10.0, 1232.0), (20.0, 1250.0)] },
+            Series { label: "<Ben>".into(), points: vec![(10.0, 1168.0)] },
+        ];
+        let svg = line_chart(&series, ("start", "end"));
+
+        let lines = polylines(&svg);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].len(), 3);
+        // Earliest point on the left edge, latest on the right, higher ratings further up
+        assert_eq!(lines[0][0].0, LEFT);
+        assert_eq!(lines[0][2].0, WIDTH - RIGHT);
+        assert!(lines[0][2].1 < lines[0][0].1 && lines[1][0].1 > lines[0][0].1);
+        for (x, y) in lines.iter().flatten() {
+            assert!((LEFT..=WIDTH - RIGHT).contains(x) && (TOP..=HEIGHT - BOTTOM).contains(y));
+        }
+
+        assert!(svg.contains("&lt;Ben&gt;") && !svg.contains("<Ben>"));
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>"));
+    }
+
+    #[test]
+    fn a_single_point_sits_in_the_middle() {
+        let series = [Series { label: "Ana".into(), points: vec![(5.0, 1200.0)] }];
+        let svg = line_chart(&series, ("", ""));
+
+        let (x, y) = polylines(&svg)[0][0];
+        assert_eq!(x, LEFT + (WIDTH - LEFT - RIGHT) / 2.0);
+        assert!((y - (TOP + (HEIGHT - TOP - BOTTOM) / 2.0)).abs() < 0.1);
+    }
+
+    #[test]
+    fn gesture_bar_splits_the_width_by_share() {
+        let svg = gesture_bar(&[(Gesture::Rock, 3), (Gesture::Paper, 0), (Gesture::Scissors, 1)]);
+
+        assert_eq!(svg.matches("<rect").count(), 2);
+        assert!(svg.contains(r#"<rect x="0.0" y="0" width="240.0""#));
+        assert!(svg.contains(r#"<rect x="240.0" y="0" width="80.0""#));
+        assert!(svg.contains("Rock 75% (3)"));
+
+        assert!(!gesture_bar(&[]).contains("<rect"));
+    }
+}
@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/* Glicko-2 Rating (Glickman, "Example of the Glicko-2 system")
+ * Results are collected for a whole rating period and applied together when the period closes.
+ * A player who sits a period out keeps the rating, but the deviation grows - we get less sure.
+*/
+
+pub const DEFAULT_GLICKO_RATING: f64 = 1500.0;
+pub const DEFAULT_DEVIATION: f64 = 350.0;
+pub const DEFAULT_VOLATILITY: f64 = 0.06;
+pub const DEFAULT_PERIOD_DAYS: u32 = 7;
+
+/* Players above this deviation are still "provisional" */
+pub const PROVISIONAL_DEVIATION: f64 = 110.0;
+
+// System constant - how much volatility may change between periods
+const TAU: f64 = 0.5;
+// Glicko <-> Glicko-2 scale
+const SCALE: f64 = 173.7178;
+const CONVERGENCE: f64 = 0.000001;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct GlickoRating {
+    pub rating: f64,
+    pub deviation: f64,
+    pub volatility: f64,
+}
+
+impl Default for GlickoRating {
+    fn default() -> Self {
+        Self {
+            rating: DEFAULT_GLICKO_RATING,
+            deviation: DEFAULT_DEVIATION,
+            volatility: DEFAULT_VOLATILITY,
+        }
+    }
+}
+
+impl GlickoRating {
+    pub fn is_provisional(&self) -> bool {
+        self.deviation > PROVISIONAL_DEVIATION
+    }
+
+    // Deviation growth for `periods` rating periods without games (capped at the starting value)
+    pub fn decayed(&self, periods: u64) -> GlickoRating {
+        let phi = self.deviation / SCALE;
+        let grown = (phi * phi + periods as f64 * self.volatility * self.volatility).sqrt() * SCALE;
+
+        GlickoRating {
+            deviation: grown.min(DEFAULT_DEVIATION),
+            ..*self
+        }
+    }
+}
+
+/* One game waiting for its rating period to close */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingGame {
+    pub player1: String,
+    pub player2: String,
+    // 1.0 Player 1 win, 0.5 tie, 0.0 Player 2 win
+    pub score_p1: f64,
+}
+
+/* Rating period bookkeeping kept in the scoreboard */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RatingPeriods {
+    pub period_days: u32,
+
+    // Index of the open period (None until the first rated game)
+    pub current_period: Option<i64>,
+
+    pub pending: Vec<PendingGame>,
+}
+
+impl Default for RatingPeriods {
+    fn default() -> Self {
+        Self {
+            period_days: DEFAULT_PERIOD_DAYS,
+            current_period: None,
+            pending: Vec::new(),
+        }
+    }
+}
+
+impl RatingPeriods {
+    // Which period a point in time falls into
+    pub fn period_of(&self, at: DateTime<Utc>) -> i64 {
+        let period_secs = self.period_days.max(1) as i64 * 24 * 60 * 60;
+        at.timestamp().div_euclid(period_secs)
+    }
+}
+
+/* Applies one closed rating period to everyone
+ * `ratings` holds every known player; players without games only get deviation growth.
+*/
+pub fn rate_period(ratings: &HashMap<String, GlickoRating>, games: &[PendingGame]) -> HashMap<String, GlickoRating> {
+    let mut updated = HashMap::new();
+
+    for (name, current) in ratings {
+        let results: Vec<(GlickoRating, f64)> = games
+            .iter()
+            .filter_map(|g| {
+                if &g.player1 == name && &g.player2 != name {
+                    ratings.get(&g.player2).map(|opp| (*opp, g.score_p1))
+                } else if &g.player2 == name && &g.player1 != name {
+                    ratings.get(&g.player1).map(|opp| (*opp, 1.0 - g.score_p1))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        let next = if results.is_empty() {
+            current.decayed(1)
+        } else {
+            rate_player(current, &results)
+        };
+        updated.insert(name.clone(), next);
+    }
+
+    updated
+}
+
+fn g(phi: f64) -> f64 {
+    1.0 / (1.0 + 3.0 * phi * phi / (PI * PI)).sqrt()
+}
+
+fn expected(mu: f64, mu_j: f64, phi_j: f64) -> f64 {
+    1.0 / (1.0 + (-g(phi_j) * (mu - mu_j)).exp())
+}
+
+/* Steps 2-8 of the Glicko-2 algorithm for one player */
+fn rate_player(player: &GlickoRating, results: &[(GlickoRating, f64)]) -> GlickoRating {
+    let mu = (player.rating - DEFAULT_GLICKO_RATING) / SCALE;
+    let phi = player.deviation / SCALE;
+    let sigma = player.volatility;
+
+    // Estimated variance (v) and improvement (delta)
+    let mut v_inv = 0.0;
+    let mut sum = 0.0;
+    for (opp, score) in results {
+        let mu_j = (opp.rating - DEFAULT_GLICKO_RATING) / SCALE;
+        let phi_j = opp.deviation / SCALE;
+        let e = expected(mu, mu_j, phi_j);
+        v_inv += g(phi_j).powi(2) * e * (1.0 - e);
+        sum += g(phi_j) * (score - e);
+    }
+    let v = 1.0 / v_inv;
+    let delta = v * sum;
+
+    // New volatility (Illinois algorithm)
+    let a = (sigma * sigma).ln();
+    let f = |x: f64| {
+        let ex = x.exp();
+        ex * (delta * delta - phi * phi - v - ex) / (2.0 * (phi * phi + v + ex).powi(2))
+            - (x - a) / (TAU * TAU)
+    };
+
+    let mut big_a = a;
+    let mut big_b = if delta * delta > phi * phi + v {
+        (delta * delta - phi * phi - v).ln()
+    } else {
+        let mut k = 1.0;
+        while f(a - k * TAU) < 0.0 {
+            k += 1.0;
+        }
+        a - k * TAU
+    };
+
+    let mut f_a = f(big_a);
+    let mut f_b = f(big_b);
+    while (big_b - big_a).abs() > CONVERGENCE {
+        let big_c = big_a + (big_a - big_b) * f_a / (f_b - f_a);
+        let f_c = f(big_c);
+        if f_c * f_b <= 0.0 {
+            big_a = big_b;
+            f_a = f_b;
+        } else {
+            f_a /= 2.0;
+        }
+        big_b = big_c;
+        f_b = f_c;
+    }
+    let new_sigma = (big_a / 2.0).exp();
+
+    // New deviation and rating
+    let phi_star = (phi * phi + new_sigma * new_sigma).sqrt();
+    let new_phi = 1.0 / (1.0 / (phi_star * phi_star) + 1.0 / v).sqrt();
+    let new_mu = mu + new_phi * new_phi * sum;
+
+    GlickoRating {
+        rating: new_mu * SCALE + DEFAULT_GLICKO_RATING,
+        deviation: (new_phi * SCALE).min(DEFAULT_DEVIATION),
+        volatility: new_sigma,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rating(rating: f64, deviation: f64) -> GlickoRating {
+        GlickoRating {
+            rating,
+            deviation,
+            volatility: DEFAULT_VOLATILITY,
+        }
+    }
+
+    fn game(player1: &str, player2: &str, score_p1: f64) -> PendingGame {
+        PendingGame {
+            player1: player1.to_string(),
+            player2: player2.to_string(),
+            score_p1,
+        }
+    }
+
+    #[test]
+    fn matches_glickmans_worked_example() {
+        let ratings = HashMap::from([
+            ("player".to_string(), rating(1500.0, 200.0)),
+            ("a".to_string(), rating(1400.0, 30.0)),
+            ("b".to_string(), rating(1550.0, 100.0)),
+            ("c".to_string(), rating(1700.0, 300.0)),
+        ]);
+        let games = [game("player", "a", 1.0), game("b", "player", 1.0), game("player", "c", 0.0)];
+
+        let rated = rate_period(&ratings, &games)["player"];
+        assert!((rated.rating - 1464.06).abs() < 0.01, "{}", rated.rating);
+        assert!((rated.deviation - 151.52).abs() < 0.01, "{}", rated.deviation);
+        assert!((rated.volatility - 0.05999).abs() < 0.00001, "{}", rated.volatility);
+    }
+
+    #[test]
+    fn idle_players_only_grow_their_deviation() {
+        let ratings = HashMap::from([
+            ("idle".to_string(), rating(1620.0, 80.0)),
+            ("a".to_string(), rating(1500.0, 350.0)),
+            ("b".to_string(), rating(1500.0, 350.0)),
+        ]);
+        let rated = rate_period(&ratings, &[game("a", "b", 0.5)]);
+
+        let idle = rated["idle"];
+        assert_eq!(idle.rating, 1620.0);
+        assert!(idle.deviation > 80.0);
+        assert_eq!(idle, ratings["idle"].decayed(1));
+    }
+
+    #[test]
+    fn decay_is_capped_at_the_starting_deviation() {
+        let settled = rating(1500.0, 50.0);
+        assert!(settled.decayed(3).deviation > settled.decayed(1).deviation);
+        assert_eq!(settled.decayed(100_000).deviation, DEFAULT_DEVIATION);
+        assert!(settled.decayed(100_000).is_provisional() && !settled.is_provisional());
+    }
+
+    #[test]
+    fn periods_split_time_into_whole_days() {
+        let periods = RatingPeriods::default();
+        let start = DateTime::from_timestamp(0, 0).unwrap();
+        let week = chrono::Duration::days(DEFAULT_PERIOD_DAYS as i64);
+
+        assert_eq!(periods.period_of(start), 0);
+        assert_eq!(periods.period_of(start + week - chrono::Duration::seconds(1)), 0);
+        assert_eq!(periods.period_of(start + week), 1);
+        assert_eq!(periods.period_of(start - chrono::Duration::seconds(1)), -1);
+    }
+}
@@ -1,2 +1,7 @@
 pub mod stats;
+#[allow(clippy::module_inception)]
 pub mod scoreboard;
+pub mod rating;
+pub mod glicko;
+pub mod tendencies;
+pub mod records;
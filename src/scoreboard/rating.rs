@@ -0,0 +1,57 @@
+/* Elo Rating
+ * Every player starts at DEFAULT_RATING, separately for each ruleset.
+ * After a match both ratings move by K * (actual score - expected score).
+*/
+
+pub const DEFAULT_RATING: f64 = 1200.0;
+pub const DEFAULT_K_FACTOR: f64 = 32.0;
+
+// Probability-like expected score of A against B (0.0 - 1.0)
+pub fn expected_score(rating_a: f64, rating_b: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((rating_b - rating_a) / 400.0))
+}
+
+// New ratings for A and B >> `score_a` is 1.0 for a win, 0.5 for a tie, 0.0 for a loss
+pub fn elo_update(rating_a: f64, rating_b: f64, score_a: f64, k_factor: f64) -> (f64, f64) {
+    let expected_a = expected_score(rating_a, rating_b);
+    let delta = k_factor * (score_a - expected_a);
+
+    (rating_a + delta, rating_b - delta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-6
+    }
+
+    #[test]
+    fn equal_ratings_expect_half_a_point() {
+        assert!(close(expected_score(1200.0, 1200.0), 0.5));
+        // 400 points apart: 10 to 1
+        assert!(close(expected_score(1600.0, 1200.0), 10.0 / 11.0));
+        assert!(close(expected_score(1200.0, 1600.0) + expected_score(1600.0, 1200.0), 1.0));
+    }
+
+    #[test]
+    fn winner_gains_what_the_loser_drops() {
+        let (a, b) = elo_update(1200.0, 1200.0, 1.0, DEFAULT_K_FACTOR);
+        assert!(close(a, 1216.0) && close(b, 1184.0));
+
+        let (a, b) = elo_update(1400.0, 1250.0, 0.0, 20.0);
+        assert!(close(a + b, 2650.0));
+        assert!(a < 1400.0 && b > 1250.0);
+    }
+
+    #[test]
+    fn a_tie_moves_unequal_ratings_towards_each_other() {
+        let (a, b) = elo_update(1600.0, 1200.0, 0.5, DEFAULT_K_FACTOR);
+        assert!(close(a, 1600.0 - 32.0 * (10.0 / 11.0 - 0.5)));
+        assert!(close(b, 1200.0 + 32.0 * (10.0 / 11.0 - 0.5)));
+
+        let (a, b) = elo_update(1300.0, 1300.0, 0.5, DEFAULT_K_FACTOR);
+        assert!(close(a, 1300.0) && close(b, 1300.0));
+    }
+}
@@ -0,0 +1,253 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::tendencies::RoundOutcome;
+use crate::game::round::RoundResult;
+use crate::history::match_record::MatchRecord;
+
+/* Win / Loss Streaks of One Player (current run + best ever) */
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Streaks {
+    // Matches
+    pub current_wins: u32,
+    pub best_wins: u32,
+    pub current_losses: u32,
+    pub best_losses: u32,
+
+    // Rounds won in a row (carries over between matches, a tie or loss ends it)
+    pub current_round_wins: u32,
+    pub best_round_wins: u32,
+}
+
+impl Streaks {
+    pub fn register_match(&mut self, won: bool, lost: bool, rounds: &[RoundOutcome]) {
+        for outcome in rounds {
+            if *outcome == RoundOutcome::Win {
+                self.current_round_wins += 1;
+                self.best_round_wins = self.best_round_wins.max(self.current_round_wins);
+            } else {
+                self.current_round_wins = 0;
+            }
+        }
+
+        if won {
+            self.current_wins += 1;
+            self.best_wins = self.best_wins.max(self.current_wins);
+            self.current_losses = 0;
+        } else if lost {
+            self.current_losses += 1;
+            self.best_losses = self.best_losses.max(self.current_losses);
+            self.current_wins = 0;
+        } else {
+            self.current_wins = 0;
+            self.current_losses = 0;
+        }
+    }
+
+    /* Profile merge: the bests carry over, the current runs can't be interleaved so keep the longer */
+    pub fn absorb(&mut self, other: &Streaks) {
+        self.current_wins = self.current_wins.max(other.current_wins);
+        self.best_wins = self.best_wins.max(other.best_wins);
+        self.current_losses = self.current_losses.max(other.current_losses);
+        self.best_losses = self.best_losses.max(other.best_losses);
+        self.current_round_wins = self.current_round_wins.max(other.current_round_wins);
+        self.best_round_wins = self.best_round_wins.max(other.best_round_wins);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordKind {
+    LongestMatch,
+    LongestTieChain,
+    BiggestComeback,
+}
+
+/* One All-Time Record and the match that set it */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordEntry {
+    pub value: u32,
+    pub match_id: String,
+    pub player1: String,
+    pub player2: String,
+    // Who set it (None when the record belongs to both, e.g. a tie chain)
+    pub holder: Option<String>,
+    pub played_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AllTimeRecords {
+    // Most rounds in one match
+    pub longest_match: Option<RecordEntry>,
+    // Most consecutive tied rounds
+    pub longest_tie_chain: Option<RecordEntry>,
+    // Biggest deficit (in points) the eventual winner came back from
+    pub biggest_comeback: Option<RecordEntry>,
+}
+
+impl AllTimeRecords {
+    /* Checks a finished match against the records >> the ones it broke */
+    pub fn register_match(&mut self, record: &MatchRecord) -> Vec<RecordKind> {
+        let mut broken = Vec::new();
+
+        let rounds = record.rounds.len() as u32;
+        if beats_record(&self.longest_match, rounds) {
+            self.longest_match = Some(entry(record, rounds, None));
+            broken.push(RecordKind::LongestMatch);
+        }
+
+        let ties = longest_tie_chain(record);
+        if beats_record(&self.longest_tie_chain, ties) {
+            self.longest_tie_chain = Some(entry(record, ties, None));
+            broken.push(RecordKind::LongestTieChain);
+        }
+
+        if let Some((winner, deficit)) = comeback(record)
+            && beats_record(&self.biggest_comeback, deficit)
+        {
+            self.biggest_comeback = Some(entry(record, deficit, Some(winner)));
+            broken.push(RecordKind::BiggestComeback);
+        }
+
+        broken
+    }
+}
+
+fn beats_record(current: &Option<RecordEntry>, value: u32) -> bool {
+    value > 0 && current.as_ref().is_none_or(|r| value > r.value)
+}
+
+fn entry(record: &MatchRecord, value: u32, holder: Option<String>) -> RecordEntry {
+    RecordEntry {
+        value,
+        match_id: record.id.clone(),
+        player1: record.player1.name.clone(),
+        player2: record.player2.name.clone(),
+        holder,
+        played_at: record.played_at,
+    }
+}
+
+fn longest_tie_chain(record: &MatchRecord) -> u32 {
+    let mut best = 0;
+    let mut current = 0;
+
+    for round in &record.rounds {
+        if round.result == RoundResult::Tie {
+            current += 1;
+            best = best.max(current);
+        } else {
+            current = 0;
+        }
+    }
+
+    best
+}
+
+/* Winner's name + the biggest point deficit they faced during the match */
+fn comeback(record: &MatchRecord) -> Option<(String, u32)> {
+    let winner = record.winner_name()?;
+    let winner_is_p1 = winner == record.player1.name;
+
+    let mut score_winner: u32 = 0;
+    let mut score_loser: u32 = 0;
+    let mut deficit = 0;
+
+    for round in &record.rounds {
+        let points = if round.bonus_point { 2 } else { 1 };
+        match (round.result, winner_is_p1) {
+            (RoundResult::Tie, _) => {}
+            (RoundResult::Player1Win, true) | (RoundResult::Player2Win, false) => score_winner += points,
+            _ => score_loser += points,
+        }
+        deficit = deficit.max(score_loser.saturating_sub(score_winner));
+    }
+
+    (deficit > 0).then_some((winner, deficit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::game_state::GameState;
+    use crate::game::match_settings::MatchFormat;
+    use crate::game::round::{resolve_round, Throw};
+    use crate::game::rules::{Gesture, Ruleset};
+    use crate::players::player::Player;
+    use crate::scoreboard::stats::MatchOutcome;
+
+    use Gesture::{Paper, Rock, Scissors};
+
+    // Ana vs Ben, one round per pair of gestures
+    fn played(rounds: &[(Gesture, Gesture)], outcome: MatchOutcome) -> MatchRecord {
+        let mut state = GameState::new(
+            Player::new_human("Ana"),
+            Player::new_human("Ben"),
+            Ruleset::Classic,
+            MatchFormat::FirstTo(3),
+            None,
+            None,
+        );
+        for (g1, g2) in rounds {
+            let throw = |gesture: &Gesture| Throw {
+                gesture: gesture.clone(),
+                reaction_ms: None,
+            };
+            resolve_round(&mut state, throw(g1), throw(g2));
+        }
+        MatchRecord::from_state(&state, outcome)
+    }
+
+    #[test]
+    fn streaks_reset_on_the_other_result() {
+        use RoundOutcome::{Loss, Tie, Win};
+
+        let mut streaks = Streaks::default();
+        streaks.register_match(true, false, &[Win, Win]);
+        streaks.register_match(true, false, &[Tie, Win, Win]);
+        assert_eq!((streaks.current_wins, streaks.best_wins), (2, 2));
+        assert_eq!((streaks.current_round_wins, streaks.best_round_wins), (2, 2));
+
+        streaks.register_match(false, true, &[Win, Loss, Loss]);
+        // Round wins carry over between matches
+        assert_eq!(streaks.best_round_wins, 3);
+        assert_eq!((streaks.current_wins, streaks.current_losses, streaks.best_losses), (0, 1, 1));
+
+        streaks.register_match(false, false, &[Tie]);
+        assert_eq!((streaks.current_wins, streaks.current_losses), (0, 0));
+        assert_eq!((streaks.best_wins, streaks.best_losses), (2, 1));
+    }
+
+    #[test]
+    fn comeback_from_two_down_sets_every_record() {
+        let record = played(
+            &[(Rock, Paper), (Rock, Paper), (Rock, Rock), (Rock, Rock), (Paper, Rock), (Paper, Rock), (Paper, Rock)],
+            MatchOutcome::Player1Win,
+        );
+        let mut records = AllTimeRecords::default();
+
+        let broken = records.register_match(&record);
+        assert_eq!(
+            broken,
+            [RecordKind::LongestMatch, RecordKind::LongestTieChain, RecordKind::BiggestComeback]
+        );
+        assert_eq!(records.longest_match.as_ref().unwrap().value, 7);
+        assert_eq!(records.longest_tie_chain.as_ref().unwrap().value, 2);
+        let comeback = records.biggest_comeback.as_ref().unwrap();
+        assert_eq!((comeback.value, comeback.holder.as_deref()), (2, Some("Ana")));
+    }
+
+    #[test]
+    fn records_need_to_be_beaten_not_matched() {
+        let mut records = AllTimeRecords::default();
+        records.register_match(&played(&[(Rock, Scissors), (Rock, Rock)], MatchOutcome::Player1Win));
+
+        // Same length and tie chain, never behind
+        let broken = records.register_match(&played(&[(Rock, Rock), (Paper, Rock)], MatchOutcome::Player1Win));
+        assert!(broken.is_empty());
+
+        // A tied match has no comeback
+        let broken = records.register_match(&played(&[(Rock, Paper), (Paper, Rock), (Rock, Rock)], MatchOutcome::Tie));
+        assert_eq!(broken, [RecordKind::LongestMatch]);
+        assert!(records.biggest_comeback.is_none());
+    }
+}
@@ -1,89 +1,609 @@
 use std::collections::HashMap;
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-use super::stats::{MatchOutcome, PlayerStats};
+use super::glicko::{rate_period, GlickoRating, PendingGame, RatingPeriods};
+use super::rating::{elo_update, DEFAULT_K_FACTOR};
+use super::records::{AllTimeRecords, RecordKind};
+use super::stats::{HeadToHead, MatchOutcome, PlayerStats};
+use super::tendencies::RoundOutcome;
+use crate::game::round::RoundResult;
+use crate::game::rules::{Gesture, Ruleset};
+use crate::history::match_record::MatchRecord;
 use crate::io::save_load::load_scoreboard_only;
+use crate::io::storage::Storage;
+use crate::players::player::{is_ai_name, Player};
+use crate::players::profile::{clean_name, normalize_name, PlayerProfile};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Scoreboard {
+    /* Stats keyed by profile ID */
     pub players: HashMap<String, PlayerStats>,
+
+    /* Who is behind each profile ID (display name + aliases) */
+    #[serde(default)]
+    pub profiles: HashMap<String, PlayerProfile>,
+
+    /* How far a single match can move an Elo rating */
+    #[serde(default = "default_k_factor")]
+    pub elo_k_factor: f64,
+
+    /* Glicko-2 games waiting for their rating period to close */
+    #[serde(default)]
+    pub rating_periods: RatingPeriods,
+
+    /* Longest match, longest tie chain, biggest comeback */
+    #[serde(default)]
+    pub records: AllTimeRecords,
+
+    /* Set on load when the file was changed outside the game: the players whose stats were edited
+     * (every player if the edit can't be pinned down). Never saved - see flag_tampered.
+    */
+    #[serde(skip)]
+    pub tampering: Option<Vec<String>>,
+}
+
+fn default_k_factor() -> f64 {
+    DEFAULT_K_FACTOR
+}
+
+// A round result seen from Player 1's (or Player 2's) side
+fn outcome_for(result: RoundResult, player1: bool) -> RoundOutcome {
+    match (result, player1) {
+        (RoundResult::Tie, _) => RoundOutcome::Tie,
+        (RoundResult::Player1Win, true) | (RoundResult::Player2Win, false) => RoundOutcome::Win,
+        _ => RoundOutcome::Loss,
+    }
 }
 
 impl Scoreboard {
     pub fn new() -> Self {
         Scoreboard {
             players: HashMap::new(),
+            profiles: HashMap::new(),
+            elo_k_factor: DEFAULT_K_FACTOR,
+            rating_periods: RatingPeriods::default(),
+            records: AllTimeRecords::default(),
+            tampering: None,
         }
     }
 
-    pub fn load() -> Self {
-        load_scoreboard_only()
+    pub fn load(storage: &dyn Storage) -> Self {
+        load_scoreboard_only(storage)
+    }
+
+    pub fn save(&self, storage: &dyn Storage) -> Result<(), String> {
+        storage.save_scoreboard(self)
+    }
+
+    /* Marks the players from `tampering` for good (the flag is saved and signed with their stats) */
+    pub fn flag_tampered(&mut self) {
+        for id in self.tampering.iter().flatten() {
+            if let Some(stats) = self.players.get_mut(id) {
+                stats.tampered = true;
+            }
+        }
+    }
+
+    /* Players whose save, achievements or logged matches were edited and kept get marked the same way */
+    pub fn flag_edited(&mut self, ids: Vec<String>) {
+        self.tampering.get_or_insert_with(Vec::new).extend(ids);
+        self.flag_tampered();
+    }
+
+    /* Profile answering to `name` (display name first, then aliases), ignoring case and spacing */
+    pub fn find_profile(&self, name: &str) -> Option<&PlayerProfile> {
+        let wanted = normalize_name(name);
+        self.profiles
+            .values()
+            .find(|p| normalize_name(&p.display_name) == wanted)
+            .or_else(|| self.profiles.values().find(|p| p.answers_to(name)))
     }
 
-    pub fn save(&self) -> Result<(), String> {
-        use crate::io::file_manager::write_to_file;
-        let json = serde_json::to_string_pretty(self)
-            .map_err(|e| format!("Serialize scoreboard failed: {e}"))?;
-        write_to_file("saves/scoreboard.json", &json)
-            .map_err(|e| format!("Write scoreboard file failed: {e}"))?;
-        Ok(())
+    // Profile ID of a match participant (None if they never finished a match)
+    pub fn profile_id(&self, player: &Player) -> Option<&str> {
+        if let Some(id) = &player.profile_id
+            && let Some(profile) = self.profiles.get(id)
+        {
+            return Some(profile.id.as_str());
+        }
+        self.find_profile(&player.name).map(|p| p.id.as_str())
+    }
+
+    pub fn display_name<'a>(&'a self, id: &'a str) -> &'a str {
+        self.profiles.get(id).map(|p| p.display_name.as_str()).unwrap_or(id)
     }
 
-    fn ensure_player(&mut self, name: &str) {
-        self.players
-            .entry(name.to_string())
-            .or_insert_with(PlayerStats::new);
+    /* Profile ID for a match participant, creating the profile on first sight */
+    pub fn ensure_player(&mut self, player: &Player) -> String {
+        let id = match self.profile_id(player) {
+            Some(id) => id.to_string(),
+            None => {
+                let profile = PlayerProfile::new(&player.name);
+                let id = profile.id.clone();
+                self.profiles.insert(id.clone(), profile);
+                id
+            }
+        };
+        self.players.entry(id.clone()).or_insert_with(PlayerStats::new);
+        id
     }
 
-    pub fn update_after_match(
-    &mut self,
-    player1_name: &str,
-    player2_name: &str,
-    rounds_won_p1: u32,
-    rounds_won_p2: u32,
-    outcome: MatchOutcome,
-) {
-    self.ensure_player(player1_name);
-    self.ensure_player(player2_name);
+    /* New profile from the setup screen >> its ID (Err if the name is already somebody's or the AI's) */
+    pub fn create_profile(&mut self, name: &str, ai_name: &str) -> Result<String, String> {
+        let name = clean_name(name);
+        if name.is_empty() {
+            return Err("The name can't be empty".to_string());
+        }
+        if is_ai_name(&name, ai_name) {
+            return Err(format!("'{name}' is reserved for the AI"));
+        }
+        if let Some(existing) = self.find_profile(&name) {
+            return Err(format!("'{name}' already belongs to {}", existing.display_name));
+        }
 
-    // Temporary take the stats out to avoid double mutable borrow
-    let mut stats1 = self.players.get(player1_name).unwrap().clone();
-    let mut stats2 = self.players.get(player2_name).unwrap().clone();
+        Ok(self.ensure_player(&Player::new_human(&name)))
+    }
 
-    match outcome {
-        MatchOutcome::Player1Win => {
-            stats1.register_match(rounds_won_p1, true);
-            stats2.register_match(rounds_won_p2, false);
+    /* Gives a profile a new display name, the old one stays as an alias */
+    pub fn rename_player(&mut self, name: &str, new_name: &str) -> Result<String, String> {
+        let new_name = clean_name(new_name);
+        if new_name.is_empty() {
+            return Err("The new name can't be empty".to_string());
         }
-        MatchOutcome::Player2Win => {
-            stats1.register_match(rounds_won_p1, false);
-            stats2.register_match(rounds_won_p2, true);
+
+        let id = self
+            .find_profile(name)
+            .map(|p| p.id.clone())
+            .ok_or_else(|| format!("No player named '{name}'"))?;
+        if let Some(other) = self.find_profile(&new_name)
+            && other.id != id
+        {
+            return Err(format!(
+                "'{new_name}' already belongs to another player - merge the two instead"
+            ));
         }
-        MatchOutcome::Tie => {
-            stats1.register_match(rounds_won_p1, false);
-            stats2.register_match(rounds_won_p2, false);
+
+        let profile = self.profiles.get_mut(&id).unwrap();
+        let old_name = std::mem::replace(&mut profile.display_name, new_name.clone());
+        profile.aliases.retain(|a| normalize_name(a) != normalize_name(&new_name));
+        profile.add_alias(&old_name);
+
+        Ok(id)
+    }
+
+    /* Folds the profile `from` into `into` >> (from ID, into ID)
+     * Stats are combined, `from`'s names become aliases of `into`, and every
+     * reference to `from` (head-to-head, pending Glicko-2 games) moves over.
+    */
+    pub fn merge_players(&mut self, from: &str, into: &str) -> Result<(String, String), String> {
+        let from_id = self
+            .find_profile(from)
+            .map(|p| p.id.clone())
+            .ok_or_else(|| format!("No player named '{from}'"))?;
+        let into_id = self
+            .find_profile(into)
+            .map(|p| p.id.clone())
+            .ok_or_else(|| format!("No player named '{into}'"))?;
+        if from_id == into_id {
+            return Err(format!("'{from}' and '{into}' are already the same player"));
         }
+
+        let merged = self.profiles.remove(&from_id).unwrap();
+        let target = self.profiles.get_mut(&into_id).unwrap();
+        target.add_alias(&merged.display_name);
+        for alias in &merged.aliases {
+            target.add_alias(alias);
+        }
+
+        let stats = self.players.remove(&from_id).unwrap_or_else(PlayerStats::new);
+        self.players.entry(into_id.clone()).or_insert_with(PlayerStats::new).absorb(&stats);
+        self.rekey(&HashMap::from([(from_id.clone(), into_id.clone())]));
+
+        Ok((from_id, into_id))
     }
 
-    // Now write back values
-    self.players.insert(player1_name.to_string(), stats1);
-    self.players.insert(player2_name.to_string(), stats2);
-}
+    /* Matches another installation's profiles to ours >> their ID >> our ID
+     * Same ID first (copied saves), then display name or alias. Known players pick up the other
+     * side's names as aliases; players we've never seen get their profile copied over.
+    */
+    pub fn adopt_profiles(&mut self, other: &Scoreboard) -> HashMap<String, String> {
+        let mut theirs: Vec<&PlayerProfile> = other.profiles.values().collect();
+        theirs.sort_by(|a, b| a.id.cmp(&b.id));
+
+        let mut ids = HashMap::new();
+        for profile in theirs {
+            let known = if self.profiles.contains_key(&profile.id) {
+                Some(profile.id.clone())
+            } else {
+                std::iter::once(&profile.display_name)
+                    .chain(&profile.aliases)
+                    .find_map(|name| self.find_profile(name))
+                    .map(|p| p.id.clone())
+            };
+
+            let id = match known {
+                Some(id) => {
+                    let ours = self.profiles.get_mut(&id).unwrap();
+                    ours.add_alias(&profile.display_name);
+                    for alias in &profile.aliases {
+                        ours.add_alias(alias);
+                    }
+                    ours.last_played = ours.last_played.max(profile.last_played);
+                    id
+                }
+                None => {
+                    self.profiles.insert(profile.id.clone(), profile.clone());
+                    profile.id.clone()
+                }
+            };
+            ids.insert(profile.id.clone(), id);
+        }
+        ids
+    }
+
+    /* Adds another scoreboard's stats to ours player by player (`ids` from adopt_profiles)
+     * Like merge_players, so ratings aren't averaged - the side with more matches keeps its own.
+    */
+    pub fn absorb_stats(&mut self, other: &Scoreboard, ids: &HashMap<String, String>) {
+        let ours = |id: &String| ids.get(id).cloned().unwrap_or_else(|| id.clone());
+
+        for (id, stats) in &other.players {
+            let id = ours(id);
+            let mut stats = stats.clone();
+            for (opponent, record) in std::mem::take(&mut stats.opponents) {
+                let opponent = ours(&opponent);
+                if opponent != id {
+                    stats.opponents.entry(opponent).or_default().absorb(&record);
+                }
+            }
+            self.players.entry(id).or_insert_with(PlayerStats::new).absorb(&stats);
+        }
+    }
+
+    /* Older scoreboards keyed stats by the name as typed ("Haris", "haris ")
+     * Gives each of those a profile; names that only differ in case or spacing end up
+     * in one profile, named after the spelling with the most matches.
+    */
+    fn migrate_legacy_names(&mut self) {
+        let mut legacy: Vec<(String, PlayerStats)> = self
+            .players
+            .iter()
+            .filter(|(key, _)| !self.profiles.contains_key(*key))
+            .map(|(key, stats)| (key.clone(), stats.clone()))
+            .collect();
+        if legacy.is_empty() {
+            return;
+        }
+        legacy.sort_by(|(a, stats_a), (b, stats_b)| {
+            stats_b.matches_played.cmp(&stats_a.matches_played).then_with(|| a.cmp(b))
+        });
+
+        let mut ids = HashMap::new();
+        for (name, stats) in legacy {
+            self.players.remove(&name);
+            let player = Player::new_human(&name);
+            let id = self.ensure_player(&player);
+            if let Some(profile) = self.profiles.get_mut(&id) {
+                profile.add_alias(&name);
+            }
+            self.players.get_mut(&id).unwrap().absorb(&stats);
+            ids.insert(name, id);
+        }
+
+        self.rekey(&ids);
+    }
+
+    // Points head-to-head entries and pending games at new keys (old key >> profile ID)
+    fn rekey(&mut self, ids: &HashMap<String, String>) {
+        for (id, stats) in self.players.iter_mut() {
+            for (opponent, record) in std::mem::take(&mut stats.opponents) {
+                let opponent = ids.get(&opponent).cloned().unwrap_or(opponent);
+                // Merging two players who met leaves games against themselves - drop those
+                if &opponent != id {
+                    stats.opponents.entry(opponent).or_default().absorb(&record);
+                }
+            }
+        }
+
+        for game in &mut self.rating_periods.pending {
+            for player in [&mut game.player1, &mut game.player2] {
+                if let Some(id) = ids.get(player) {
+                    *player = id.clone();
+                }
+            }
+        }
+    }
+
+    /* Folds a finished match into everyone's stats and ratings >> all-time records it broke */
+    pub fn update_after_match(&mut self, record: &MatchRecord) -> Vec<RecordKind> {
+        let ruleset = &record.ruleset;
+        let outcome = record.outcome;
+
+        let player1_id = self.ensure_player(&record.player1);
+        let player2_id = self.ensure_player(&record.player2);
+
+        // Temporary take the stats out to avoid double mutable borrow
+        let mut stats1 = self.players.get(&player1_id).unwrap().clone();
+        let mut stats2 = self.players.get(&player2_id).unwrap().clone();
 
+        // Rounds, not points - a blitz bonus scores 2 for a single round
+        let count = |r: RoundResult| record.rounds.iter().filter(|x| x.result == r).count() as u32;
+        let (p1_rounds, p2_rounds, tied_rounds) = (
+            count(RoundResult::Player1Win),
+            count(RoundResult::Player2Win),
+            count(RoundResult::Tie),
+        );
+        stats1.register_match(p1_rounds, outcome == MatchOutcome::Player1Win);
+        stats2.register_match(p2_rounds, outcome == MatchOutcome::Player2Win);
+
+        /* Head-to-head */
+        stats1.opponents.entry(player2_id.clone()).or_default().register_match(
+            (p1_rounds, p2_rounds, tied_rounds),
+            outcome == MatchOutcome::Player1Win,
+            outcome == MatchOutcome::Player2Win,
+        );
+        stats2.opponents.entry(player1_id.clone()).or_default().register_match(
+            (p2_rounds, p1_rounds, tied_rounds),
+            outcome == MatchOutcome::Player2Win,
+            outcome == MatchOutcome::Player1Win,
+        );
+
+        /* Gesture tendencies */
+        let rounds_p1: Vec<(Option<Gesture>, RoundOutcome)> = record
+            .rounds
+            .iter()
+            .map(|r| (r.gesture_p1.clone(), outcome_for(r.result, true)))
+            .collect();
+        let rounds_p2: Vec<(Option<Gesture>, RoundOutcome)> = record
+            .rounds
+            .iter()
+            .map(|r| (r.gesture_p2.clone(), outcome_for(r.result, false)))
+            .collect();
+        stats1.tendencies.register_match(&rounds_p1);
+        stats2.tendencies.register_match(&rounds_p2);
+
+        /* Streaks */
+        let outcomes = |rounds: &[(Option<Gesture>, RoundOutcome)]| -> Vec<RoundOutcome> {
+            rounds.iter().map(|(_, o)| *o).collect()
+        };
+        stats1.streaks.register_match(
+            outcome == MatchOutcome::Player1Win,
+            outcome == MatchOutcome::Player2Win,
+            &outcomes(&rounds_p1),
+        );
+        stats2.streaks.register_match(
+            outcome == MatchOutcome::Player2Win,
+            outcome == MatchOutcome::Player1Win,
+            &outcomes(&rounds_p2),
+        );
+
+        // Matches logged before this only timed outside blitz too - those times don't count
+        for round in record.rounds.iter().filter(|_| record.blitz.is_some()) {
+            if let Some(ms) = round.reaction_ms_p1 {
+                stats1.register_reaction_time(ms);
+            }
+            if let Some(ms) = round.reaction_ms_p2 {
+                stats2.register_reaction_time(ms);
+            }
+        }
+
+        /* Elo for this ruleset */
+        let score_p1 = match outcome {
+            MatchOutcome::Player1Win => 1.0,
+            MatchOutcome::Player2Win => 0.0,
+            MatchOutcome::Tie => 0.5,
+        };
+        let (rating1, rating2) = elo_update(
+            stats1.rating(ruleset),
+            stats2.rating(ruleset),
+            score_p1,
+            self.elo_k_factor,
+        );
+        stats1.set_rating(ruleset, rating1);
+        stats2.set_rating(ruleset, rating2);
+
+        // Now write back values
+        self.players.insert(player1_id.clone(), stats1);
+        self.players.insert(player2_id.clone(), stats2);
+
+        for id in [&player1_id, &player2_id] {
+            if let Some(profile) = self.profiles.get_mut(id) {
+                profile.last_played = profile.last_played.max(Some(record.played_at));
+            }
+        }
+
+        /* Glicko-2: queue the game for the rating period it was played in */
+        self.close_rating_periods(record.played_at);
+        if self.rating_periods.current_period.is_none() {
+            self.rating_periods.current_period = Some(self.rating_periods.period_of(record.played_at));
+        }
+        self.rating_periods.pending.push(PendingGame {
+            player1: player1_id,
+            player2: player2_id,
+            score_p1,
+        });
+
+        self.records.register_match(record)
+    }
+
+    /* Stats rebuilt from scratch by replaying `records` (oldest first), rating periods closed up to now
+     * Profiles, settings and the tampered marks are kept.
+    */
+    pub fn replayed(&self, records: &[MatchRecord]) -> Scoreboard {
+        let mut rebuilt = Scoreboard {
+            players: HashMap::new(),
+            profiles: self.profiles.clone(),
+            elo_k_factor: self.elo_k_factor,
+            rating_periods: RatingPeriods {
+                period_days: self.rating_periods.period_days,
+                ..RatingPeriods::default()
+            },
+            records: AllTimeRecords::default(),
+            tampering: None,
+        };
+
+        let mut ordered: Vec<&MatchRecord> = records.iter().collect();
+        ordered.sort_by_key(|r| r.played_at);
+        for record in ordered {
+            rebuilt.update_after_match(record);
+        }
+        rebuilt.close_rating_periods(Utc::now());
+        rebuilt.keep_tamper_flags(self);
+        rebuilt
+    }
+
+    /* Players edited outside the game stay marked, whichever scoreboard the mark came from */
+    pub fn keep_tamper_flags(&mut self, other: &Scoreboard) {
+        for (id, stats) in &other.players {
+            if stats.tampered
+                && let Some(ours) = self.players.get_mut(id)
+            {
+                ours.tampered = true;
+            }
+        }
+    }
+
+    /* Bragging Rights for the Victory Screen (streaks + broken records) */
+    pub fn match_announcements(&self, record: &MatchRecord, broken: &[RecordKind]) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        for player in [&record.player1, &record.player2] {
+            let Some(stats) = self.profile_id(player).and_then(|id| self.players.get(id)) else {
+                continue;
+            };
+            let name = &player.name;
+            if stats.streaks.current_wins >= 2 {
+                lines.push(format!(
+                    "🔥 {name} is on a {}-match win streak!",
+                    stats.streaks.current_wins
+                ));
+            }
+            if stats.streaks.current_wins >= 3 && stats.streaks.current_wins == stats.streaks.best_wins {
+                lines.push(format!("🔥 That's {name}'s best win streak ever!"));
+            }
+        }
+
+        for kind in broken {
+            let line = match kind {
+                RecordKind::LongestMatch => format!(
+                    "🏆 New all-time record: longest match ({} rounds)!",
+                    record.rounds.len()
+                ),
+                RecordKind::LongestTieChain => self
+                    .records
+                    .longest_tie_chain
+                    .as_ref()
+                    .map(|r| format!("🏆 New all-time record: longest tie chain ({} rounds)!", r.value))
+                    .unwrap_or_default(),
+                RecordKind::BiggestComeback => self
+                    .records
+                    .biggest_comeback
+                    .as_ref()
+                    .map(|r| {
+                        format!(
+                            "🏆 New all-time record: {} came back from {} points down!",
+                            r.holder.as_deref().unwrap_or("?"),
+                            r.value
+                        )
+                    })
+                    .unwrap_or_default(),
+            };
+            lines.push(line);
+        }
+
+        lines
+    }
+
+    /* Applies every Glicko-2 rating period that has ended by `now`
+     * The open period is rated with its games, every later (empty) period only grows deviations.
+    */
+    pub fn close_rating_periods(&mut self, now: DateTime<Utc>) {
+        let Some(current) = self.rating_periods.current_period else {
+            return;
+        };
+
+        let now_period = self.rating_periods.period_of(now);
+        if now_period <= current {
+            return;
+        }
+
+        self.rate_open_period((now_period - current - 1) as u64);
+        self.rating_periods.current_period = Some(now_period);
+    }
+
+    /* Takes the rating settings from the config >> true if anything changed
+     * A new period length counts from `now`; games of the open period are rated before the switch.
+    */
+    pub fn apply_rating_settings(
+        &mut self,
+        elo_k_factor: Option<f64>,
+        period_days: Option<u32>,
+        now: DateTime<Utc>,
+    ) -> bool {
+        let mut changed = false;
+        if let Some(k) = elo_k_factor
+            && k != self.elo_k_factor
+        {
+            self.elo_k_factor = k;
+            changed = true;
+        }
+
+        if let Some(days) = period_days
+            && days != self.rating_periods.period_days
+        {
+            self.close_rating_periods(now);
+            if self.rating_periods.current_period.is_some() {
+                if !self.rating_periods.pending.is_empty() {
+                    self.rate_open_period(0);
+                }
+                self.rating_periods.period_days = days;
+                self.rating_periods.current_period = Some(self.rating_periods.period_of(now));
+            } else {
+                self.rating_periods.period_days = days;
+            }
+            changed = true;
+        }
+        changed
+    }
+
+    // Rates the pending games, then grows everyone's deviation for `idle_periods` empty periods
+    fn rate_open_period(&mut self, idle_periods: u64) {
+        let ratings: HashMap<String, GlickoRating> = self
+            .players
+            .iter()
+            .map(|(name, stats)| (name.clone(), stats.glicko))
+            .collect();
+        let rated = rate_period(&ratings, &self.rating_periods.pending);
+
+        for (name, rating) in rated {
+            if let Some(stats) = self.players.get_mut(&name) {
+                stats.glicko = rating.decayed(idle_periods);
+            }
+        }
+
+        self.rating_periods.pending.clear();
+    }
+
+    // Record of `player` against `opponent`, both profile IDs (None if they never met)
+    pub fn head_to_head(&self, player: &str, opponent: &str) -> Option<&HeadToHead> {
+        self.players.get(player)?.opponents.get(opponent)
+    }
 
     // Returns players sorted by matches won (descending)
     pub fn sorted_by_wins(&self) -> Vec<(&str, &PlayerStats)> {
         let mut v: Vec<(&str, &PlayerStats)> =
-            self.players.iter().map(|(k, v)| (k.as_str(), v)).collect();
-        v.sort_by(|(_, a), (_, b)| b.matches_won.cmp(&a.matches_won));
+            self.players.iter().map(|(k, v)| (self.display_name(k), v)).collect();
+        v.sort_by_key(|(_, s)| std::cmp::Reverse(s.matches_won));
         v
     }
 
     // Returns players sorted by win rate (descending)
     pub fn sorted_by_win_rate(&self) -> Vec<(&str, &PlayerStats)> {
         let mut v: Vec<(&str, &PlayerStats)> =
-            self.players.iter().map(|(k, v)| (k.as_str(), v)).collect();
+            self.players.iter().map(|(k, v)| (self.display_name(k), v)).collect();
 
         v.sort_by(|(_, a), (_, b)| {
             let rate_a = if a.matches_played > 0 {
@@ -103,4 +623,149 @@ impl Scoreboard {
 
         v
     }
+
+    // Returns players rated in the ruleset, sorted by Elo rating (descending)
+    pub fn sorted_by_rating(&self, ruleset: &Ruleset) -> Vec<(&str, &PlayerStats)> {
+        let mut v: Vec<(&str, &PlayerStats)> = self
+            .players
+            .iter()
+            .filter(|(_, stats)| stats.ratings.contains_key(ruleset))
+            .map(|(k, v)| (self.display_name(k), v))
+            .collect();
+
+        v.sort_by(|(_, a), (_, b)| {
+            b.rating(ruleset)
+                .partial_cmp(&a.rating(ruleset))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        v
+    }
+
+    // Returns players sorted by Glicko-2 rating (descending), optionally without provisional players
+    pub fn sorted_by_glicko(&self, hide_provisional: bool) -> Vec<(&str, &PlayerStats)> {
+        let mut v: Vec<(&str, &PlayerStats)> = self
+            .players
+            .iter()
+            .filter(|(_, s)| !(hide_provisional && s.glicko.is_provisional()))
+            .map(|(k, v)| (self.display_name(k), v))
+            .collect();
+
+        v.sort_by(|(_, a), (_, b)| {
+            b.glicko
+                .rating
+                .partial_cmp(&a.glicko.rating)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        v
+    }
+}
+
+/* Scoreboard v1 >> v2: stats keyed by name become player profiles (see envelope::migrations) */
+pub fn migrate_scoreboard_v1_to_v2(data: Value) -> Result<Value, String> {
+    let mut scoreboard: Scoreboard = serde_json::from_value(data).map_err(|e| e.to_string())?;
+    scoreboard.migrate_legacy_names();
+    serde_json::to_value(&scoreboard).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::game_state::GameState;
+    use crate::game::match_settings::{BlitzSettings, MatchFormat};
+    use crate::game::round::{resolve_round, Throw};
+
+    fn throw(gesture: Gesture, reaction_ms: u64) -> Throw {
+        Throw {
+            gesture,
+            reaction_ms: Some(reaction_ms),
+        }
+    }
+
+    // One-round match between two humans, decided by `gestures`
+    fn play(sb: &mut Scoreboard, players: (&str, &str), ruleset: Ruleset, gestures: (Gesture, Gesture)) {
+        let mut state = GameState::new(
+            Player::new_human(players.0),
+            Player::new_human(players.1),
+            ruleset,
+            MatchFormat::SingleRound,
+            None,
+            None,
+        );
+        let outcome = match resolve_round(&mut state, throw(gestures.0, 0), throw(gestures.1, 0)) {
+            RoundResult::Player1Win => MatchOutcome::Player1Win,
+            RoundResult::Player2Win => MatchOutcome::Player2Win,
+            RoundResult::Tie => MatchOutcome::Tie,
+        };
+        sb.update_after_match(&MatchRecord::from_state(&state, outcome));
+    }
+
+    fn names(sorted: Vec<(&str, &PlayerStats)>) -> Vec<String> {
+        sorted.into_iter().map(|(name, _)| name.to_string()).collect()
+    }
+
+    #[test]
+    fn rating_leaderboard_only_lists_players_of_that_ruleset() {
+        let mut sb = Scoreboard::new();
+        play(&mut sb, ("Ana", "Ben"), Ruleset::Classic, (Gesture::Rock, Gesture::Scissors));
+        play(&mut sb, ("Cid", "Ana"), Ruleset::Extended, (Gesture::Spock, Gesture::Rock));
+
+        assert_eq!(names(sb.sorted_by_rating(&Ruleset::Classic)), ["Ana", "Ben"]);
+        assert_eq!(names(sb.sorted_by_rating(&Ruleset::Extended)), ["Cid", "Ana"]);
+
+        let ana = &sb.players[&sb.find_profile("Ana").unwrap().id];
+        assert_eq!(ana.rating(&Ruleset::Classic), 1216.0);
+        assert_eq!(ana.rating(&Ruleset::Extended), 1184.0);
+    }
+
+    #[test]
+    fn head_to_head_is_kept_from_both_sides() {
+        let mut sb = Scoreboard::new();
+        play(&mut sb, ("Ana", "Ben"), Ruleset::Classic, (Gesture::Rock, Gesture::Scissors));
+        play(&mut sb, ("Ben", "Ana"), Ruleset::Classic, (Gesture::Rock, Gesture::Paper));
+        play(&mut sb, ("Ana", "Ben"), Ruleset::Extended, (Gesture::Spock, Gesture::Lizard));
+        play(&mut sb, ("Ana", "Cid"), Ruleset::Classic, (Gesture::Rock, Gesture::Rock));
+
+        let ana = sb.find_profile("Ana").unwrap().id.clone();
+        let ben = sb.find_profile("Ben").unwrap().id.clone();
+        let cid = sb.find_profile("Cid").unwrap().id.clone();
+
+        let ana_vs_ben = sb.head_to_head(&ana, &ben).unwrap();
+        assert_eq!((ana_vs_ben.matches_played, ana_vs_ben.matches_won), (3, 2));
+        assert_eq!(ana_vs_ben.longest_streak, 2);
+        let ben_vs_ana = sb.head_to_head(&ben, &ana).unwrap();
+        assert_eq!((ben_vs_ana.matches_lost, ben_vs_ana.rounds_lost), (2, 2));
+        assert_eq!(ben_vs_ana.rounds_won, ana_vs_ben.rounds_lost);
+
+        let ana_vs_cid = sb.head_to_head(&ana, &cid).unwrap();
+        assert_eq!((ana_vs_cid.matches_won, ana_vs_cid.matches_lost, ana_vs_cid.rounds_tied), (0, 0, 1));
+        assert!(sb.head_to_head(&ben, &cid).is_none());
+    }
+
+    #[test]
+    fn blitz_bonus_points_are_not_counted_as_rounds_won() {
+        let mut state = GameState::new(
+            Player::new_human("Ana"),
+            Player::new_human("Ben"),
+            Ruleset::Classic,
+            MatchFormat::FirstTo(3),
+            None,
+            Some(BlitzSettings { sub_second_bonus: true }),
+        );
+        // Sub-second win (2 points), then a slow one (1 point)
+        resolve_round(&mut state, throw(Gesture::Rock, 400), throw(Gesture::Scissors, 900));
+        resolve_round(&mut state, throw(Gesture::Paper, 1500), throw(Gesture::Rock, 1800));
+        assert_eq!(state.score_player1, 3);
+
+        let record = MatchRecord::from_state(&state, MatchOutcome::Player1Win);
+        let mut sb = Scoreboard::new();
+        sb.update_after_match(&record);
+
+        let ana = sb.players.get(sb.find_profile("Ana").unwrap().id.as_str()).unwrap();
+        assert_eq!(ana.rounds_won, 2);
+        assert_eq!(ana.matches_won, 1);
+        let ben = sb.players.get(sb.find_profile("Ben").unwrap().id.as_str()).unwrap();
+        assert_eq!(ben.rounds_won, 0);
+    }
 }
@@ -1,10 +1,48 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
+use super::glicko::GlickoRating;
+use super::rating::DEFAULT_RATING;
+use super::records::Streaks;
+use super::tendencies::GestureTendencies;
+use crate::game::rules::Ruleset;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlayerStats {
     pub matches_played: u32,
     pub matches_won: u32,
     pub rounds_won: u32,
+
+    /* Sum of all timed gesture entries (ms) and how many there were */
+    #[serde(default)]
+    pub reaction_time_total_ms: u64,
+    #[serde(default)]
+    pub timed_moves: u32,
+
+    /* Elo rating per ruleset (missing = never played it, DEFAULT_RATING) */
+    #[serde(default)]
+    pub ratings: HashMap<Ruleset, f64>,
+
+    /* Glicko-2 rating across all rulesets (only moves when a rating period closes) */
+    #[serde(default)]
+    pub glicko: GlickoRating,
+
+    /* Results against each opponent (keyed by the opponent's profile ID) */
+    #[serde(default)]
+    pub opponents: HashMap<String, HeadToHead>,
+
+    /* Lifetime gesture frequencies and what follows a win / loss / tie */
+    #[serde(default)]
+    pub tendencies: GestureTendencies,
+
+    /* Current and best win / loss / round-win streaks */
+    #[serde(default)]
+    pub streaks: Streaks,
+
+    /* Stats were edited outside the game at some point */
+    #[serde(default)]
+    pub tampered: bool,
 }
 
 impl PlayerStats {
@@ -13,6 +51,14 @@ impl PlayerStats {
             matches_played: 0,
             matches_won: 0,
             rounds_won: 0,
+            reaction_time_total_ms: 0,
+            timed_moves: 0,
+            ratings: HashMap::new(),
+            glicko: GlickoRating::default(),
+            opponents: HashMap::new(),
+            tendencies: GestureTendencies::default(),
+            streaks: Streaks::default(),
+            tampered: false,
         }
     }
 
@@ -23,11 +69,144 @@ impl PlayerStats {
             self.matches_won += 1;
         }
     }
+
+    pub fn register_reaction_time(&mut self, reaction_ms: u64) {
+        self.reaction_time_total_ms += reaction_ms;
+        self.timed_moves += 1;
+    }
+
+    pub fn rating(&self, ruleset: &Ruleset) -> f64 {
+        self.ratings.get(ruleset).copied().unwrap_or(DEFAULT_RATING)
+    }
+
+    pub fn set_rating(&mut self, ruleset: &Ruleset, rating: f64) {
+        self.ratings.insert(ruleset.clone(), rating);
+    }
+
+    /* Adds another profile's stats to these (profile merge)
+     * Counters add up. Ratings can't be combined meaningfully, so the side
+     * with more matches played keeps its Elo and Glicko-2 ratings.
+    */
+    pub fn absorb(&mut self, other: &PlayerStats) {
+        if other.matches_played > self.matches_played {
+            self.ratings = other.ratings.clone();
+            self.glicko = other.glicko;
+        } else {
+            for (ruleset, rating) in &other.ratings {
+                self.ratings.entry(ruleset.clone()).or_insert(*rating);
+            }
+        }
+
+        self.matches_played += other.matches_played;
+        self.matches_won += other.matches_won;
+        self.rounds_won += other.rounds_won;
+        self.reaction_time_total_ms += other.reaction_time_total_ms;
+        self.timed_moves += other.timed_moves;
+
+        for (opponent, record) in &other.opponents {
+            self.opponents.entry(opponent.clone()).or_default().absorb(record);
+        }
+        self.tendencies.absorb(&other.tendencies);
+        self.streaks.absorb(&other.streaks);
+        self.tampered |= other.tampered;
+    }
+
+    // Average time to enter a gesture (None until a move was timed)
+    pub fn average_reaction_ms(&self) -> Option<u64> {
+        if self.timed_moves > 0 {
+            Some(self.reaction_time_total_ms / self.timed_moves as u64)
+        } else {
+            None
+        }
+    }
+}
+
+/* Record Against One Opponent (from this player's point of view) */
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HeadToHead {
+    pub matches_played: u32,
+    pub matches_won: u32,
+    pub matches_lost: u32,
+
+    pub rounds_won: u32,
+    pub rounds_lost: u32,
+    pub rounds_tied: u32,
+
+    // Consecutive match wins right now, and the best run ever
+    pub current_streak: u32,
+    pub longest_streak: u32,
+}
+
+impl HeadToHead {
+    pub fn register_match(&mut self, rounds: (u32, u32, u32), won: bool, lost: bool) {
+        let (rounds_won, rounds_lost, rounds_tied) = rounds;
+
+        self.matches_played += 1;
+        self.rounds_won += rounds_won;
+        self.rounds_lost += rounds_lost;
+        self.rounds_tied += rounds_tied;
+
+        if won {
+            self.matches_won += 1;
+            self.current_streak += 1;
+            self.longest_streak = self.longest_streak.max(self.current_streak);
+        } else {
+            if lost {
+                self.matches_lost += 1;
+            }
+            self.current_streak = 0;
+        }
+    }
+
+    pub fn absorb(&mut self, other: &HeadToHead) {
+        self.matches_played += other.matches_played;
+        self.matches_won += other.matches_won;
+        self.matches_lost += other.matches_lost;
+        self.rounds_won += other.rounds_won;
+        self.rounds_lost += other.rounds_lost;
+        self.rounds_tied += other.rounds_tied;
+        self.current_streak = self.current_streak.max(other.current_streak);
+        self.longest_streak = self.longest_streak.max(other.longest_streak);
+    }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MatchOutcome {
     Player1Win,
     Player2Win,
     Tie,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn head_to_head_counts_rounds_and_the_win_streak() {
+        let mut record = HeadToHead::default();
+        record.register_match((2, 1, 0), true, false);
+        record.register_match((3, 0, 1), true, false);
+        record.register_match((1, 1, 2), false, false);
+        record.register_match((2, 0, 0), true, false);
+
+        assert_eq!((record.matches_played, record.matches_won, record.matches_lost), (4, 3, 0));
+        assert_eq!((record.rounds_won, record.rounds_lost, record.rounds_tied), (8, 2, 3));
+        // The tie broke the run
+        assert_eq!((record.current_streak, record.longest_streak), (1, 2));
+    }
+
+    #[test]
+    fn merged_head_to_head_keeps_the_longer_streaks() {
+        let mut ours = HeadToHead::default();
+        ours.register_match((1, 0, 0), true, false);
+        let mut theirs = HeadToHead::default();
+        for _ in 0..3 {
+            theirs.register_match((1, 0, 0), true, false);
+        }
+        theirs.register_match((0, 1, 0), false, true);
+
+        ours.absorb(&theirs);
+        assert_eq!((ours.matches_played, ours.matches_won, ours.matches_lost), (5, 4, 1));
+        assert_eq!((ours.current_streak, ours.longest_streak), (1, 3));
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::rules::Gesture;
+
+/* How a round ended for one player */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundOutcome {
+    Win,
+    Loss,
+    Tie,
+}
+
+/* Lifetime Gesture Habits of One Player
+ * `after_*` count what was thrown in the round following a win / loss / tie in the same match.
+*/
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GestureTendencies {
+    pub counts: HashMap<Gesture, u32>,
+    pub after_win: HashMap<Gesture, u32>,
+    pub after_loss: HashMap<Gesture, u32>,
+    pub after_tie: HashMap<Gesture, u32>,
+}
+
+impl GestureTendencies {
+    /* One match worth of rounds (None = no gesture, e.g. forfeited on time) */
+    pub fn register_match(&mut self, rounds: &[(Option<Gesture>, RoundOutcome)]) {
+        let mut previous: Option<RoundOutcome> = None;
+
+        for (gesture, outcome) in rounds {
+            if let Some(g) = gesture {
+                *self.counts.entry(g.clone()).or_insert(0) += 1;
+
+                let follow_up = match previous {
+                    Some(RoundOutcome::Win) => Some(&mut self.after_win),
+                    Some(RoundOutcome::Loss) => Some(&mut self.after_loss),
+                    Some(RoundOutcome::Tie) => Some(&mut self.after_tie),
+                    None => None,
+                };
+                if let Some(counts) = follow_up {
+                    *counts.entry(g.clone()).or_insert(0) += 1;
+                }
+            }
+            previous = Some(*outcome);
+        }
+    }
+
+    /* Adds another player's habits to these (profile merge) */
+    pub fn absorb(&mut self, other: &GestureTendencies) {
+        for (mine, theirs) in [
+            (&mut self.counts, &other.counts),
+            (&mut self.after_win, &other.after_win),
+            (&mut self.after_loss, &other.after_loss),
+            (&mut self.after_tie, &other.after_tie),
+        ] {
+            for (gesture, count) in theirs {
+                *mine.entry(gesture.clone()).or_insert(0) += count;
+            }
+        }
+    }
+
+    pub fn total(&self) -> u32 {
+        self.counts.values().sum()
+    }
+
+    /* Shannon entropy of the gesture mix in bits (0 = always the same gesture) */
+    pub fn entropy(&self) -> f64 {
+        entropy(&self.counts)
+    }
+
+    /* 0% = perfectly random, 100% = always the same gesture
+     * Measured against the most random mix possible: 3 gestures, or 5 once Lizard/Spock show up.
+    */
+    pub fn predictability(&self) -> Option<f64> {
+        if self.total() == 0 {
+            return None;
+        }
+
+        let extended = self.counts.contains_key(&Gesture::Lizard) || self.counts.contains_key(&Gesture::Spock);
+        let max_entropy = if extended { 5f64.log2() } else { 3f64.log2() };
+
+        Some((1.0 - self.entropy() / max_entropy).clamp(0.0, 1.0))
+    }
+}
+
+fn entropy(counts: &HashMap<Gesture, u32>) -> f64 {
+    let total: u32 = counts.values().sum();
+    if total == 0 {
+        return 0.0;
+    }
+
+    counts
+        .values()
+        .filter(|&&c| c > 0)
+        .map(|&c| {
+            let p = c as f64 / total as f64;
+            -p * p.log2()
+        })
+        .sum::<f64>()
+        .abs()
+}
+
+/* Gestures with their share of `counts`, most frequent first */
+pub fn frequencies(counts: &HashMap<Gesture, u32>) -> Vec<(Gesture, f64)> {
+    let total: u32 = counts.values().sum();
+    if total == 0 {
+        return Vec::new();
+    }
+
+    let mut v: Vec<(Gesture, f64)> = counts
+        .iter()
+        .map(|(g, c)| (g.clone(), *c as f64 / total as f64))
+        .collect();
+    v.sort_by(|(ga, a), (gb, b)| {
+        b.partial_cmp(a)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| ga.as_str().cmp(gb.as_str()))
+    });
+    v
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tendencies(gestures: &[Gesture]) -> GestureTendencies {
+        let rounds: Vec<(Option<Gesture>, RoundOutcome)> =
+            gestures.iter().map(|g| (Some(g.clone()), RoundOutcome::Tie)).collect();
+        let mut t = GestureTendencies::default();
+        t.register_match(&rounds);
+        t
+    }
+
+    #[test]
+    fn follow_ups_are_counted_per_match() {
+        let mut t = GestureTendencies::default();
+        t.register_match(&[
+            (Some(Gesture::Rock), RoundOutcome::Win),
+            (Some(Gesture::Rock), RoundOutcome::Loss),
+            (None, RoundOutcome::Loss),
+            (Some(Gesture::Paper), RoundOutcome::Tie),
+        ]);
+        // A new match doesn't follow the last round of the previous one
+        t.register_match(&[(Some(Gesture::Scissors), RoundOutcome::Win)]);
+
+        assert_eq!(t.total(), 4);
+        assert_eq!(t.after_win, HashMap::from([(Gesture::Rock, 1)]));
+        assert_eq!(t.after_loss, HashMap::from([(Gesture::Paper, 1)]));
+        assert!(t.after_tie.is_empty());
+    }
+
+    #[test]
+    fn predictability_runs_from_random_to_one_gesture() {
+        assert_eq!(GestureTendencies::default().predictability(), None);
+        assert_eq!(tendencies(&vec![Gesture::Rock; 4]).predictability(), Some(1.0));
+
+        let even = tendencies(&[Gesture::Rock, Gesture::Paper, Gesture::Scissors]);
+        assert!((even.entropy() - 3f64.log2()).abs() < 1e-9);
+        assert!(even.predictability().unwrap() < 1e-9);
+    }
+
+    #[test]
+    fn lizard_or_spock_measures_against_five_gestures() {
+        let classic = tendencies(&[Gesture::Rock, Gesture::Paper, Gesture::Scissors]);
+        let extended = tendencies(&[Gesture::Rock, Gesture::Paper, Gesture::Spock]);
+
+        assert_eq!(classic.entropy(), extended.entropy());
+        let expected = 1.0 - 3f64.log2() / 5f64.log2();
+        assert!((extended.predictability().unwrap() - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn frequencies_are_shares_sorted_by_count() {
+        let t = tendencies(&[Gesture::Paper, Gesture::Rock, Gesture::Paper, Gesture::Scissors]);
+        let shares = frequencies(&t.counts);
+
+        assert_eq!(shares[0], (Gesture::Paper, 0.5));
+        // Ties in count go by name
+        assert_eq!(shares[1..], [(Gesture::Rock, 0.25), (Gesture::Scissors, 0.25)]);
+        assert!(frequencies(&HashMap::new()).is_empty());
+    }
+}
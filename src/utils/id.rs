@@ -0,0 +1,6 @@
+use rand::random;
+
+/* Random 16-char hex ID (matches, profiles) - unique enough to merge logs from several machines */
+pub fn new_id() -> String {
+    format!("{:016x}", random::<u64>())
+}
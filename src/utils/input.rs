@@ -1,19 +1,75 @@
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::Instant;
 
 use crate::game::rules::{Gesture, Ruleset};
 
+/* Single Background Reader for STDIN
+ * A blocking read_line can't be cancelled, so every prompt reads from this channel instead.
+ * That way a timed-out prompt doesn't leave a stray read behind that steals the next line.
+*/
+fn stdin_lines() -> &'static Mutex<Receiver<String>> {
+    static LINES: OnceLock<Mutex<Receiver<String>>> = OnceLock::new();
+
+    LINES.get_or_init(|| {
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let stdin = io::stdin();
+            loop {
+                let mut line = String::new();
+                match stdin.read_line(&mut line) {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => {
+                        if tx.send(line).is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+
+        Mutex::new(rx)
+    })
+}
+
 /* Reads Input */
 pub fn read_input(prompt: &str) -> String {
     print!("{prompt}");
     io::stdout().flush().unwrap();
 
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).unwrap();
-    input.trim().to_string()  
+    let input = stdin_lines().lock().unwrap().recv().unwrap_or_default();
+    input.trim().to_string()
+}
+
+/* Reads Input Until `deadline` >> None if the time ran out (or STDIN closed) */
+pub fn read_input_until(prompt: &str, deadline: Instant) -> Option<String> {
+    print!("{prompt}");
+    io::stdout().flush().unwrap();
+
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    match stdin_lines().lock().unwrap().recv_timeout(remaining) {
+        Ok(input) => Some(input.trim().to_string()),
+        Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Disconnected) => None,
+    }
 }
 
+/* Throws away lines typed before a prompt was shown (e.g. typed too late for the previous one)
+ * Piped input is left alone - there every buffered line is meant for a later prompt.
+*/
+pub fn discard_pending_input() {
+    if !io::stdin().is_terminal() {
+        return;
+    }
+
+    let lines = stdin_lines().lock().unwrap();
+    while lines.try_recv().is_ok() {}
+}
 
 /* Reads Input & Turns them into Lowercase (for commands, gestures, y/n answers) */
+#[allow(dead_code)]
 pub fn read_input_lowercase(prompt: &str) -> String {
     read_input(prompt).to_lowercase()
 }
@@ -41,6 +97,21 @@ pub fn read_number(prompt: &str) -> u32 {
     }
 }
 
+/* Like read_number, but an empty line picks `default` */
+pub fn read_number_or(prompt: &str, default: u32) -> u32 {
+    loop {
+        let input = read_input(prompt);
+        if input.is_empty() {
+            return default;
+        }
+
+        match input.parse::<u32>() {
+            Ok(n) => return n,
+            Err(_) => println!("Please enter a valid number."),
+        }
+    }
+}
+
 pub fn parse_gesture(input: &str, ruleset: &Ruleset) -> Option<Gesture> {
     match input.to_lowercase().as_str() { 
         "r" | "rock" => Some(Gesture::Rock),
@@ -54,6 +125,7 @@ pub fn parse_gesture(input: &str, ruleset: &Ruleset) -> Option<Gesture> {
     }
 }
 
+#[allow(dead_code)]
 pub fn read_gesture(prompt: &str, ruleset: &Ruleset) -> Gesture {
     loop {
         let input = read_input(prompt);  
@@ -71,4 +143,4 @@ pub fn read_gesture(prompt: &str, ruleset: &Ruleset) -> Gesture {
             }
         }
     }
-}
\ No newline at end of file
+}
@@ -1,2 +1,3 @@
 pub mod input;
 pub mod clear_screen;
+pub mod id;